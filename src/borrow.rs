@@ -12,12 +12,25 @@ pub trait TryBorrow<Borrowed: ?Sized> {
 }
 
 /// The try trait for [`BorrowMut`].
+///
+/// Mutable borrows can fail for different reasons than shared ones (eg the value is read-only, or
+/// it's already borrowed), so they get their own error type. If you want the old behaviour, set
+/// `type MutError = <Self as TryBorrow<Borrowed>>::Error;` (which is the default when the
+/// `nightly_associated_type_defaults` feature is enabled).
 pub trait TryBorrowMut<Borrowed: ?Sized> : TryBorrow<Borrowed> {
-	/// The fallible equivalent of [`BorrowMut::try_borrowMut`].
-	fn try_borrow_mut(&mut self) -> Result<&mut Borrowed, <Self as TryBorrow<Borrowed>>::Error>;
+	/// The type returned in the event of an error when borrowing mutably.
+	#[cfg(feature = "nightly_associated_type_defaults")]
+	type MutError = <Self as TryBorrow<Borrowed>>::Error;
+
+	/// The type returned in the event of an error when borrowing mutably.
+	#[cfg(not(feature = "nightly_associated_type_defaults"))]
+	type MutError;
+
+	/// The fallible equivalent of [`BorrowMut::borrow_mut`].
+	fn try_borrow_mut(&mut self) -> Result<&mut Borrowed, Self::MutError>;
 }
 
-impl<T: Borrow<Borrowed>, Borrowed: ?Sized> TryBorrow<Borrowed> for T {
+impl<T: ?Sized + Borrow<Borrowed>, Borrowed: ?Sized> TryBorrow<Borrowed> for T {
 	type Error = crate::Infallible;
 
 	#[inline]
@@ -26,9 +39,11 @@ impl<T: Borrow<Borrowed>, Borrowed: ?Sized> TryBorrow<Borrowed> for T {
 	}
 }
 
-impl<T: BorrowMut<Borrowed>, Borrowed: ?Sized> TryBorrowMut<Borrowed> for T {
+impl<T: ?Sized + BorrowMut<Borrowed>, Borrowed: ?Sized> TryBorrowMut<Borrowed> for T {
+	type MutError = crate::Infallible;
+
 	#[inline]
-	fn try_borrow_mut(&mut self) -> Result<&mut Borrowed, Self::Error> {
+	fn try_borrow_mut(&mut self) -> Result<&mut Borrowed, Self::MutError> {
 		Ok(self.borrow_mut())
 	}
 }
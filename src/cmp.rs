@@ -1,6 +1,14 @@
 //! Try traits for [`core::cmp`].
 
 use core::cmp::{Ord, PartialOrd, Ordering};
+use core::fmt;
+
+mod comparator;
+mod lexicographic;
+pub mod poset;
+
+pub use comparator::*;
+pub use lexicographic::*;
 
 /// The try trait for [`PartialEq`].
 pub trait TryPartialEq<Rhs: ?Sized = Self> {
@@ -23,38 +31,54 @@ pub trait TryPartialEq<Rhs: ?Sized = Self> {
 pub trait TryEq : TryPartialEq<Self> {}
 
 /// The try trait for [`PartialOrd`].
+///
+/// Ordering can fail for different reasons than equality (eg needing collation data that equality
+/// doesn't), so it has its own error type. Equality errors must be convertible into it, so that
+/// implementations can defer to [`try_eq`](TryPartialEq::try_eq) with `?`. If you want a single
+/// error type, set `type OrdError = <Self as TryPartialEq<Rhs>>::Error;` (which is the default when
+/// the `nightly_associated_type_defaults` feature is enabled).
 pub trait TryPartialOrd<Rhs: ?Sized = Self> : TryPartialEq<Rhs> {
+	/// The type returned in the event of an error when ordering.
+	#[cfg(feature = "nightly_associated_type_defaults")]
+	type OrdError: From<<Self as TryPartialEq<Rhs>>::Error> = <Self as TryPartialEq<Rhs>>::Error;
+
+	/// The type returned in the event of an error when ordering.
+	#[cfg(not(feature = "nightly_associated_type_defaults"))]
+	type OrdError: From<<Self as TryPartialEq<Rhs>>::Error>;
+
 	/// The fallible equivalent of [`PartialOrd::partial_cmp`].
-	fn try_partial_cmp(&self, other: &Rhs) -> Result<Option<Ordering>, Self::Error>;
+	fn try_partial_cmp(&self, other: &Rhs) -> Result<Option<Ordering>, Self::OrdError>;
 
 	/// The fallible equivalent of [`PartialOrd::lt`].
-	fn try_lt(&self, other: &Rhs) -> Result<bool, Self::Error> {
+	fn try_lt(&self, other: &Rhs) -> Result<bool, Self::OrdError> {
 		Ok(matches!(self.try_partial_cmp(other)?, Some(Ordering::Less)))
 	}
 
 	/// The fallible equivalent of [`PartialOrd::le`].
-	fn try_le(&self, other: &Rhs) -> Result<bool, Self::Error> {
-		Ok(!matches!(self.try_partial_cmp(other)?, Some(Ordering::Greater)))
+	fn try_le(&self, other: &Rhs) -> Result<bool, Self::OrdError> {
+		Ok(matches!(self.try_partial_cmp(other)?, Some(Ordering::Less) | Some(Ordering::Equal)))
 	}
 
 	/// The fallible equivalent of [`PartialOrd::gt`].
-	fn try_gt(&self, other: &Rhs) -> Result<bool, Self::Error> {
+	fn try_gt(&self, other: &Rhs) -> Result<bool, Self::OrdError> {
 		Ok(matches!(self.try_partial_cmp(other)?, Some(Ordering::Greater)))
 	}
 
 	/// The fallible equivalent of [`PartialOrd::gt`].
-	fn try_ge(&self, other: &Rhs) -> Result<bool, Self::Error> {
-		Ok(!matches!(self.try_partial_cmp(other)?, Some(Ordering::Less)))
+	fn try_ge(&self, other: &Rhs) -> Result<bool, Self::OrdError> {
+		Ok(matches!(self.try_partial_cmp(other)?, Some(Ordering::Greater) | Some(Ordering::Equal)))
 	}
 }
 
 /// The try trait for [`Ord`].
+///
+/// Errors are reported using [`TryPartialOrd::OrdError`].
 pub trait TryOrd : TryEq + TryPartialOrd<Self> {
 	/// The fallible equivalent of [`Ord::cmp`].
-	fn try_cmp(&self, other: &Self) -> Result<Ordering, Self::Error>;
+	fn try_cmp(&self, other: &Self) -> Result<Ordering, Self::OrdError>;
 
 	/// The fallible equivalent of [`Ord::max`].
-	fn try_max(self, other: Self) -> Result<Self, Self::Error>
+	fn try_max(self, other: Self) -> Result<Self, Self::OrdError>
 	where
 		Self: Sized
 	{
@@ -65,7 +89,7 @@ pub trait TryOrd : TryEq + TryPartialOrd<Self> {
 	}
 
 	/// The fallible equivalent of [`Ord::min`].
-	fn try_min(self, other: Self) -> Result<Self, Self::Error>
+	fn try_min(self, other: Self) -> Result<Self, Self::OrdError>
 	where
 		Self: Sized
 	{
@@ -77,10 +101,13 @@ pub trait TryOrd : TryEq + TryPartialOrd<Self> {
 
 	/// The fallible equivalent of [`Ord::clamp`].
 	///
+	/// This doesn't check that `min <= max`; use [`try_clamp_checked`](TryOrd::try_clamp_checked)
+	/// if that's needed.
+	///
 	/// # Panics
 	///
-	/// Panics if `min > max`
-	fn try_clamp(self, min: Self, max: Self) -> Result<Self, Self::Error>
+	/// Implementations may panic if `min > max` (as the one for [`Ord`] types does).
+	fn try_clamp(self, min: Self, max: Self) -> Result<Self, Self::OrdError>
 	where
 		Self: Sized
 	{
@@ -92,9 +119,95 @@ pub trait TryOrd : TryEq + TryPartialOrd<Self> {
 			Ok(self)
 		}
 	}
+
+	/// The same as [`try_clamp`](TryOrd::try_clamp), except it returns
+	/// [`ClampError::InvalidBounds`] instead of panicking if `min > max`.
+	fn try_clamp_checked(self, min: Self, max: Self) -> Result<Self, ClampError<Self::OrdError>>
+	where
+		Self: Sized
+	{
+		if !is_le(&min, &max).map_err(ClampError::Error)? {
+			return Err(ClampError::InvalidBounds);
+		}
+
+		self.try_clamp(min, max).map_err(ClampError::Error)
+	}
+}
+
+/// Returns whether `lhs <= rhs`, treating incomparable values as not (regardless of how `try_le`
+/// is implemented).
+fn is_le<T: ?Sized + TryPartialOrd>(lhs: &T, rhs: &T) -> Result<bool, T::OrdError> {
+	Ok(matches!(lhs.try_partial_cmp(rhs)?, Some(Ordering::Less) | Some(Ordering::Equal)))
 }
 
-impl<T: PartialEq<Rhs>, Rhs: ?Sized> TryPartialEq<Rhs> for T {
+/// The error returned by [`TryOrd::try_clamp_checked`] and [`TryRange::try_new`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClampError<E> {
+	/// The lower bound was greater than the upper bound.
+	InvalidBounds,
+
+	/// Comparing the values failed.
+	Error(E),
+}
+
+impl<E: fmt::Display> fmt::Display for ClampError<E> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::InvalidBounds => write!(f, "lower bound is greater than upper bound"),
+			Self::Error(err) => fmt::Display::fmt(err, f)
+		}
+	}
+}
+
+/// An inclusive range whose bounds have been checked with [`TryPartialOrd`].
+///
+/// This is the fallible equivalent of [`RangeInclusive`](core::ops::RangeInclusive), except that
+/// inverted ranges (where `start > end`) are rejected when it's constructed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TryRange<T> {
+	start: T,
+	end: T
+}
+
+impl<T: TryPartialOrd> TryRange<T> {
+	/// Creates a new range, returning [`ClampError::InvalidBounds`] if `start > end` or if they
+	/// aren't comparable.
+	pub fn try_new(start: T, end: T) -> Result<Self, ClampError<T::OrdError>> {
+		if is_le(&start, &end).map_err(ClampError::Error)? {
+			Ok(Self { start, end })
+		} else {
+			Err(ClampError::InvalidBounds)
+		}
+	}
+
+	/// The fallible equivalent of [`RangeInclusive::contains`](
+	/// core::ops::RangeInclusive::contains).
+	pub fn try_contains(&self, item: &T) -> Result<bool, T::OrdError> {
+		Ok(is_le(&self.start, item)? && is_le(item, &self.end)?)
+	}
+}
+
+impl<T> TryRange<T> {
+	/// Returns the lower bound of the range.
+	#[inline]
+	pub fn start(&self) -> &T {
+		&self.start
+	}
+
+	/// Returns the upper bound of the range.
+	#[inline]
+	pub fn end(&self) -> &T {
+		&self.end
+	}
+
+	/// Destructures the range into `(start, end)`.
+	#[inline]
+	pub fn into_inner(self) -> (T, T) {
+		(self.start, self.end)
+	}
+}
+
+impl<T: ?Sized + PartialEq<Rhs>, Rhs: ?Sized> TryPartialEq<Rhs> for T {
 	type Error = crate::Infallible;
 
 	#[inline]
@@ -108,43 +221,45 @@ impl<T: PartialEq<Rhs>, Rhs: ?Sized> TryPartialEq<Rhs> for T {
 	}
 }
 
-impl<T: Eq> TryEq for T {}
+impl<T: ?Sized + Eq> TryEq for T {}
+
+impl<T: ?Sized + PartialOrd<Rhs>, Rhs: ?Sized> TryPartialOrd<Rhs> for T {
+	type OrdError = crate::Infallible;
 
-impl<T: PartialOrd<Rhs>, Rhs: ?Sized> TryPartialOrd<Rhs> for T {
 	#[inline]
-	fn try_partial_cmp(&self, other: &Rhs) -> Result<Option<Ordering>, Self::Error> {
+	fn try_partial_cmp(&self, other: &Rhs) -> Result<Option<Ordering>, Self::OrdError> {
 		Ok(self.partial_cmp(other))
 	}
 
 	#[inline]
-	fn try_lt(&self, other: &Rhs) -> Result<bool, Self::Error> {
+	fn try_lt(&self, other: &Rhs) -> Result<bool, Self::OrdError> {
 		Ok(self < other)
 	}
 
 	#[inline]
-	fn try_le(&self, other: &Rhs) -> Result<bool, Self::Error> {
+	fn try_le(&self, other: &Rhs) -> Result<bool, Self::OrdError> {
 		Ok(self <= other)
 	}
 
 	#[inline]
-	fn try_gt(&self, other: &Rhs) -> Result<bool, Self::Error> {
+	fn try_gt(&self, other: &Rhs) -> Result<bool, Self::OrdError> {
 		Ok(self > other)
 	}
 
 	#[inline]
-	fn try_ge(&self, other: &Rhs) -> Result<bool, Self::Error> {
+	fn try_ge(&self, other: &Rhs) -> Result<bool, Self::OrdError> {
 		Ok(self >= other)
 	}
 }
 
-impl<T: Ord> TryOrd for T {
+impl<T: ?Sized + Ord> TryOrd for T {
 	#[inline]
-	fn try_cmp(&self, other: &Self) -> Result<Ordering, Self::Error> {
+	fn try_cmp(&self, other: &Self) -> Result<Ordering, Self::OrdError> {
 		Ok(self.cmp(other))
 	}
 
 	#[inline]
-	fn try_max(self, other: Self) -> Result<Self, Self::Error>
+	fn try_max(self, other: Self) -> Result<Self, Self::OrdError>
 	where
 		Self: Sized
 	{
@@ -152,14 +267,14 @@ impl<T: Ord> TryOrd for T {
 	}
 
 	#[inline]
-	fn try_min(self, other: Self) -> Result<Self, Self::Error>
+	fn try_min(self, other: Self) -> Result<Self, Self::OrdError>
 	where
 		Self: Sized
 	{
 		Ok(self.min(other))
 	}
 
-	fn try_clamp(self, min: Self, max: Self) -> Result<Self, Self::Error>
+	fn try_clamp(self, min: Self, max: Self) -> Result<Self, Self::OrdError>
 	where
 		Self: Sized
 	{
@@ -179,3 +294,192 @@ impl<T: Ord> TryOrd for T {
 		}
 	}
 }
+
+/// The fallible equivalent of [`core::cmp::max`].
+///
+/// Returns `v2` if the comparison determines them to be equal.
+#[inline]
+pub fn try_max<T: TryOrd>(v1: T, v2: T) -> Result<T, T::OrdError> {
+	v1.try_max(v2)
+}
+
+/// The fallible equivalent of [`core::cmp::min`].
+///
+/// Returns `v1` if the comparison determines them to be equal.
+#[inline]
+pub fn try_min<T: TryOrd>(v1: T, v2: T) -> Result<T, T::OrdError> {
+	v1.try_min(v2)
+}
+
+/// The fallible equivalent of [`core::cmp::max_by`].
+///
+/// Returns `v2` if the comparison determines them to be equal.
+pub fn try_max_by<T, E, F>(v1: T, v2: T, compare: F) -> Result<T, E>
+where
+	F: FnOnce(&T, &T) -> Result<Ordering, E>
+{
+	match compare(&v1, &v2)? {
+		Ordering::Less | Ordering::Equal => Ok(v2),
+		Ordering::Greater => Ok(v1)
+	}
+}
+
+/// The fallible equivalent of [`core::cmp::min_by`].
+///
+/// Returns `v1` if the comparison determines them to be equal.
+pub fn try_min_by<T, E, F>(v1: T, v2: T, compare: F) -> Result<T, E>
+where
+	F: FnOnce(&T, &T) -> Result<Ordering, E>
+{
+	match compare(&v1, &v2)? {
+		Ordering::Less | Ordering::Equal => Ok(v1),
+		Ordering::Greater => Ok(v2)
+	}
+}
+
+/// The fallible equivalent of [`core::cmp::max_by_key`].
+///
+/// Returns `v2` if the comparison determines them to be equal. Comparison errors are converted
+/// into the key function's error type.
+pub fn try_max_by_key<T, K, E, F>(v1: T, v2: T, mut f: F) -> Result<T, E>
+where
+	F: FnMut(&T) -> Result<K, E>,
+	K: TryOrd,
+	K::OrdError: Into<E>
+{
+	let (k1, k2) = (f(&v1)?, f(&v2)?);
+
+	try_max_by(v1, v2, |_, _| k1.try_cmp(&k2).map_err(Into::into))
+}
+
+/// The fallible equivalent of [`core::cmp::min_by_key`].
+///
+/// Returns `v1` if the comparison determines them to be equal. Comparison errors are converted
+/// into the key function's error type.
+pub fn try_min_by_key<T, K, E, F>(v1: T, v2: T, mut f: F) -> Result<T, E>
+where
+	F: FnMut(&T) -> Result<K, E>,
+	K: TryOrd,
+	K::OrdError: Into<E>
+{
+	let (k1, k2) = (f(&v1)?, f(&v2)?);
+
+	try_min_by(v1, v2, |_, _| k1.try_cmp(&k2).map_err(Into::into))
+}
+
+/// A helper struct for reverse ordering; the fallible equivalent of [`core::cmp::Reverse`].
+///
+/// This inverts the [`TryPartialOrd`] and [`TryOrd`] implementations of the inner type, which is
+/// useful for sorting in descending order by keys that can fail to compare.
+#[derive(Debug, Clone, Copy, Default, Hash)]
+#[repr(transparent)]
+pub struct TryReverse<T>(pub T);
+
+impl<T: TryPartialEq> TryPartialEq for TryReverse<T> {
+	type Error = T::Error;
+
+	#[inline]
+	fn try_eq(&self, other: &Self) -> Result<bool, Self::Error> {
+		self.0.try_eq(&other.0)
+	}
+
+	#[inline]
+	fn try_ne(&self, other: &Self) -> Result<bool, Self::Error> {
+		self.0.try_ne(&other.0)
+	}
+}
+
+impl<T: TryEq> TryEq for TryReverse<T> {}
+
+impl<T: TryPartialOrd> TryPartialOrd for TryReverse<T> {
+	type OrdError = T::OrdError;
+
+	#[inline]
+	fn try_partial_cmp(&self, other: &Self) -> Result<Option<Ordering>, Self::OrdError> {
+		other.0.try_partial_cmp(&self.0)
+	}
+
+	#[inline]
+	fn try_lt(&self, other: &Self) -> Result<bool, Self::OrdError> {
+		other.0.try_lt(&self.0)
+	}
+
+	#[inline]
+	fn try_le(&self, other: &Self) -> Result<bool, Self::OrdError> {
+		other.0.try_le(&self.0)
+	}
+
+	#[inline]
+	fn try_gt(&self, other: &Self) -> Result<bool, Self::OrdError> {
+		other.0.try_gt(&self.0)
+	}
+
+	#[inline]
+	fn try_ge(&self, other: &Self) -> Result<bool, Self::OrdError> {
+		other.0.try_ge(&self.0)
+	}
+}
+
+impl<T: TryOrd> TryOrd for TryReverse<T> {
+	#[inline]
+	fn try_cmp(&self, other: &Self) -> Result<Ordering, Self::OrdError> {
+		other.0.try_cmp(&self.0)
+	}
+}
+
+/// Converts a [`TryPartialOrd`] type into a [`TryOrd`] one, by treating incomparable values as
+/// errors.
+///
+/// Equality is also determined via [`try_partial_cmp`](TryPartialOrd::try_partial_cmp), so that
+/// incomparable values (such as `NaN`s) are reported as errors instead of being unequal to
+/// themselves; this means that whenever `try_eq` returns `Ok`, the requirements of [`Eq`] hold.
+#[derive(Debug, Clone, Copy, Default, Hash)]
+#[repr(transparent)]
+pub struct Totalize<T>(pub T);
+
+/// The error type used by [`Totalize`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TotalizeError<E> {
+	/// The two values weren't comparable (ie `try_partial_cmp` returned `Ok(None)`).
+	Incomparable,
+
+	/// Comparing the values failed.
+	Error(E),
+}
+
+impl<E: fmt::Display> fmt::Display for TotalizeError<E> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Incomparable => write!(f, "values are incomparable"),
+			Self::Error(err) => fmt::Display::fmt(err, f)
+		}
+	}
+}
+
+impl<T: TryPartialOrd> TryPartialEq for Totalize<T> {
+	type Error = TotalizeError<T::OrdError>;
+
+	#[inline]
+	fn try_eq(&self, other: &Self) -> Result<bool, Self::Error> {
+		Ok(self.try_cmp(other)? == Ordering::Equal)
+	}
+}
+
+impl<T: TryPartialOrd> TryEq for Totalize<T> {}
+
+impl<T: TryPartialOrd> TryPartialOrd for Totalize<T> {
+	type OrdError = TotalizeError<T::OrdError>;
+
+	#[inline]
+	fn try_partial_cmp(&self, other: &Self) -> Result<Option<Ordering>, Self::OrdError> {
+		self.try_cmp(other).map(Some)
+	}
+}
+
+impl<T: TryPartialOrd> TryOrd for Totalize<T> {
+	fn try_cmp(&self, other: &Self) -> Result<Ordering, Self::OrdError> {
+		self.0.try_partial_cmp(&other.0)
+			.map_err(TotalizeError::Error)?
+			.ok_or(TotalizeError::Incomparable)
+	}
+}
@@ -0,0 +1,165 @@
+use core::cmp::Ordering;
+use super::TryOrd;
+
+/// Fallible chaining methods for [`Ordering`].
+pub trait TryOrderingExt : Sized {
+	/// The fallible equivalent of [`Ordering::then_with`].
+	///
+	/// If `self` is [`Equal`](Ordering::Equal), `f` is called to break the tie. Otherwise, `self`
+	/// is returned and `f` is never called.
+	fn try_then_with<E, F>(self, f: F) -> Result<Ordering, E>
+	where
+		F: FnOnce() -> Result<Ordering, E>;
+}
+
+impl TryOrderingExt for Ordering {
+	#[inline]
+	fn try_then_with<E, F>(self, f: F) -> Result<Ordering, E>
+	where
+		F: FnOnce() -> Result<Ordering, E>
+	{
+		match self {
+			Ordering::Equal => f(),
+			other => Ok(other)
+		}
+	}
+}
+
+/// A fallible comparison function, usually built with [`try_comparing`].
+///
+/// This is implemented for every `Fn(&T, &T) -> Result<Ordering, E>`, so closures can be used
+/// directly wherever a comparator is expected, and every comparator can be turned back into a
+/// closure via [`comparator_fn`].
+pub trait TryComparator<T: ?Sized> {
+	/// The type returned in the event of an error.
+	type Error;
+
+	/// Compares `lhs` and `rhs`.
+	fn try_compare(&self, lhs: &T, rhs: &T) -> Result<Ordering, Self::Error>;
+
+	/// Returns a comparator that uses `next` to break ties left by `self`.
+	///
+	/// Errors from `next` are converted into `Self::Error`.
+	fn then<C>(self, next: C) -> Then<Self, C>
+	where
+		Self: Sized,
+		C: TryComparator<T>,
+		C::Error: Into<Self::Error>
+	{
+		Then { first: self, next }
+	}
+
+	/// Returns a comparator that breaks ties left by `self` by comparing the keys `key` returns.
+	///
+	/// This is shorthand for `self.then(try_comparing(key))`.
+	fn then_try_comparing<F, K, E>(self, key: F) -> Then<Self, TryComparing<F>>
+	where
+		Self: Sized,
+		F: Fn(&T) -> Result<K, E>,
+		K: TryOrd,
+		K::OrdError: Into<E>,
+		E: Into<Self::Error>
+	{
+		self.then(try_comparing(key))
+	}
+
+	/// Returns a comparator that orders things in the opposite order as `self`.
+	fn reversed(self) -> Reversed<Self>
+	where
+		Self: Sized
+	{
+		Reversed(self)
+	}
+}
+
+impl<T: ?Sized, E, F> TryComparator<T> for F
+where
+	F: Fn(&T, &T) -> Result<Ordering, E>
+{
+	type Error = E;
+
+	#[inline]
+	fn try_compare(&self, lhs: &T, rhs: &T) -> Result<Ordering, Self::Error> {
+		self(lhs, rhs)
+	}
+}
+
+/// Creates a comparator that compares the keys that `key` returns using [`TryOrd::try_cmp`].
+///
+/// Both the key extraction and the comparison can fail; comparison errors are converted into the
+/// key extraction's error type. (Note this means that if the keys are compared infallibly, `E` must
+/// implement `From<`[`Infallible`](crate::Infallible)`>`, which
+/// [`impl_from_infallible!`](crate::impl_from_infallible) can do.)
+pub fn try_comparing<T, F, K, E>(key: F) -> TryComparing<F>
+where
+	T: ?Sized,
+	F: Fn(&T) -> Result<K, E>,
+	K: TryOrd,
+	K::OrdError: Into<E>
+{
+	TryComparing(key)
+}
+
+/// Converts `comparator` into a closure.
+pub fn comparator_fn<T, C>(comparator: C) -> impl Fn(&T, &T) -> Result<Ordering, C::Error>
+where
+	T: ?Sized,
+	C: TryComparator<T>
+{
+	move |lhs, rhs| comparator.try_compare(lhs, rhs)
+}
+
+/// The comparator returned by [`try_comparing`].
+#[derive(Debug, Clone, Copy)]
+pub struct TryComparing<F>(F);
+
+impl<T, F, K, E> TryComparator<T> for TryComparing<F>
+where
+	T: ?Sized,
+	F: Fn(&T) -> Result<K, E>,
+	K: TryOrd,
+	K::OrdError: Into<E>
+{
+	type Error = E;
+
+	fn try_compare(&self, lhs: &T, rhs: &T) -> Result<Ordering, Self::Error> {
+		let lhs = (self.0)(lhs)?;
+		let rhs = (self.0)(rhs)?;
+
+		lhs.try_cmp(&rhs).map_err(Into::into)
+	}
+}
+
+/// The comparator returned by [`TryComparator::then`].
+#[derive(Debug, Clone, Copy)]
+pub struct Then<A, B> {
+	first: A,
+	next: B
+}
+
+impl<T: ?Sized, A, B> TryComparator<T> for Then<A, B>
+where
+	A: TryComparator<T>,
+	B: TryComparator<T>,
+	B::Error: Into<A::Error>
+{
+	type Error = A::Error;
+
+	fn try_compare(&self, lhs: &T, rhs: &T) -> Result<Ordering, Self::Error> {
+		self.first.try_compare(lhs, rhs)?
+			.try_then_with(|| self.next.try_compare(lhs, rhs).map_err(Into::into))
+	}
+}
+
+/// The comparator returned by [`TryComparator::reversed`].
+#[derive(Debug, Clone, Copy)]
+pub struct Reversed<C>(C);
+
+impl<T: ?Sized, C: TryComparator<T>> TryComparator<T> for Reversed<C> {
+	type Error = C::Error;
+
+	#[inline]
+	fn try_compare(&self, lhs: &T, rhs: &T) -> Result<Ordering, Self::Error> {
+		self.0.try_compare(rhs, lhs)
+	}
+}
@@ -0,0 +1,312 @@
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Formatter};
+use core::marker::PhantomData;
+use super::{TryPartialEq, TryEq, TryPartialOrd, TryOrd};
+
+/// Compares tuples, arrays, slices, [`Option`]s and [`Result`]s lexicographically, using the try
+/// traits of their elements.
+///
+/// The semantics are exactly those of the std implementations, except that errors are propagated.
+/// The elements' errors are converted into `E` (see the
+/// [`structural`](crate::structural#error-types) module docs, which also explain why this is a
+/// wrapper).
+#[repr(transparent)]
+pub struct Lexicographic<T, E>(pub T, PhantomData<fn() -> E>);
+
+impl<T, E> Lexicographic<T, E> {
+	/// Wraps `value`.
+	#[inline]
+	pub const fn new(value: T) -> Self {
+		Self(value, PhantomData)
+	}
+
+	/// Unwraps the value.
+	#[inline]
+	pub fn into_inner(self) -> T {
+		self.0
+	}
+}
+
+impl<T: Debug, E> Debug for Lexicographic<T, E> {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.debug_tuple("Lexicographic").field(&self.0).finish()
+	}
+}
+
+impl<T: Clone, E> Clone for Lexicographic<T, E> {
+	#[inline]
+	fn clone(&self) -> Self {
+		Self::new(self.0.clone())
+	}
+}
+
+impl<T: Copy, E> Copy for Lexicographic<T, E> {}
+
+fn slice_try_eq<T: TryPartialEq, E>(lhs: &[T], rhs: &[T]) -> Result<bool, E>
+where
+	T::Error: Into<E>
+{
+	if lhs.len() != rhs.len() {
+		return Ok(false);
+	}
+
+	for (lhs, rhs) in lhs.iter().zip(rhs) {
+		if !lhs.try_eq(rhs).map_err(Into::into)? {
+			return Ok(false);
+		}
+	}
+
+	Ok(true)
+}
+
+fn slice_try_partial_cmp<T: TryPartialOrd, E>(lhs: &[T], rhs: &[T]) -> Result<Option<Ordering>, E>
+where
+	T::OrdError: Into<E>
+{
+	for (lhs, rhs) in lhs.iter().zip(rhs) {
+		match lhs.try_partial_cmp(rhs).map_err(Into::into)? {
+			Some(Ordering::Equal) => {},
+			ordering => return Ok(ordering)
+		}
+	}
+
+	Ok(lhs.len().partial_cmp(&rhs.len()))
+}
+
+fn slice_try_cmp<T: TryOrd, E>(lhs: &[T], rhs: &[T]) -> Result<Ordering, E>
+where
+	T::OrdError: Into<E>
+{
+	for (lhs, rhs) in lhs.iter().zip(rhs) {
+		match lhs.try_cmp(rhs).map_err(Into::into)? {
+			Ordering::Equal => {},
+			ordering => return Ok(ordering)
+		}
+	}
+
+	Ok(lhs.len().cmp(&rhs.len()))
+}
+
+macro_rules! impl_slice_like {
+	($([$($generics:tt)*] $ty:ty),* $(,)?) => {$(
+		impl<$($generics)*, T: TryPartialEq, E> TryPartialEq for Lexicographic<$ty, E>
+		where
+			T::Error: Into<E>
+		{
+			type Error = E;
+
+			#[inline]
+			fn try_eq(&self, other: &Self) -> Result<bool, Self::Error> {
+				slice_try_eq(&self.0[..], &other.0[..])
+			}
+		}
+
+		impl<$($generics)*, T: TryEq, E> TryEq for Lexicographic<$ty, E>
+		where
+			T::Error: Into<E>
+		{}
+
+		impl<$($generics)*, T: TryPartialOrd, E> TryPartialOrd for Lexicographic<$ty, E>
+		where
+			T::Error: Into<E>,
+			T::OrdError: Into<E>
+		{
+			type OrdError = E;
+
+			#[inline]
+			fn try_partial_cmp(&self, other: &Self) -> Result<Option<Ordering>, Self::OrdError> {
+				slice_try_partial_cmp(&self.0[..], &other.0[..])
+			}
+		}
+
+		impl<$($generics)*, T: TryOrd, E> TryOrd for Lexicographic<$ty, E>
+		where
+			T::Error: Into<E>,
+			T::OrdError: Into<E>
+		{
+			#[inline]
+			fn try_cmp(&self, other: &Self) -> Result<Ordering, Self::OrdError> {
+				slice_try_cmp(&self.0[..], &other.0[..])
+			}
+		}
+	)*};
+}
+
+impl_slice_like!([const N: usize] [T; N], ['a] &'a [T], ['a] &'a mut [T]);
+
+impl<T: TryPartialEq, E> TryPartialEq for Lexicographic<Option<T>, E>
+where
+	T::Error: Into<E>
+{
+	type Error = E;
+
+	fn try_eq(&self, other: &Self) -> Result<bool, Self::Error> {
+		match (&self.0, &other.0) {
+			(Some(lhs), Some(rhs)) => lhs.try_eq(rhs).map_err(Into::into),
+			(None, None) => Ok(true),
+			_ => Ok(false)
+		}
+	}
+}
+
+impl<T: TryEq, E> TryEq for Lexicographic<Option<T>, E>
+where
+	T::Error: Into<E>
+{}
+
+impl<T: TryPartialOrd, E> TryPartialOrd for Lexicographic<Option<T>, E>
+where
+	T::Error: Into<E>,
+	T::OrdError: Into<E>
+{
+	type OrdError = E;
+
+	fn try_partial_cmp(&self, other: &Self) -> Result<Option<Ordering>, Self::OrdError> {
+		match (&self.0, &other.0) {
+			(Some(lhs), Some(rhs)) => lhs.try_partial_cmp(rhs).map_err(Into::into),
+			(lhs, rhs) => Ok(lhs.is_some().partial_cmp(&rhs.is_some()))
+		}
+	}
+}
+
+impl<T: TryOrd, E> TryOrd for Lexicographic<Option<T>, E>
+where
+	T::Error: Into<E>,
+	T::OrdError: Into<E>
+{
+	fn try_cmp(&self, other: &Self) -> Result<Ordering, Self::OrdError> {
+		match (&self.0, &other.0) {
+			(Some(lhs), Some(rhs)) => lhs.try_cmp(rhs).map_err(Into::into),
+			(lhs, rhs) => Ok(lhs.is_some().cmp(&rhs.is_some()))
+		}
+	}
+}
+
+impl<T: TryPartialEq, U: TryPartialEq, E> TryPartialEq for Lexicographic<Result<T, U>, E>
+where
+	T::Error: Into<E>,
+	U::Error: Into<E>
+{
+	type Error = E;
+
+	fn try_eq(&self, other: &Self) -> Result<bool, Self::Error> {
+		match (&self.0, &other.0) {
+			(Ok(lhs), Ok(rhs)) => lhs.try_eq(rhs).map_err(Into::into),
+			(Err(lhs), Err(rhs)) => lhs.try_eq(rhs).map_err(Into::into),
+			_ => Ok(false)
+		}
+	}
+}
+
+impl<T: TryEq, U: TryEq, E> TryEq for Lexicographic<Result<T, U>, E>
+where
+	T::Error: Into<E>,
+	U::Error: Into<E>
+{}
+
+impl<T: TryPartialOrd, U: TryPartialOrd, E> TryPartialOrd for Lexicographic<Result<T, U>, E>
+where
+	T::Error: Into<E>,
+	U::Error: Into<E>,
+	T::OrdError: Into<E>,
+	U::OrdError: Into<E>
+{
+	type OrdError = E;
+
+	fn try_partial_cmp(&self, other: &Self) -> Result<Option<Ordering>, Self::OrdError> {
+		match (&self.0, &other.0) {
+			(Ok(lhs), Ok(rhs)) => lhs.try_partial_cmp(rhs).map_err(Into::into),
+			(Err(lhs), Err(rhs)) => lhs.try_partial_cmp(rhs).map_err(Into::into),
+			(lhs, rhs) => Ok(lhs.is_err().partial_cmp(&rhs.is_err()))
+		}
+	}
+}
+
+impl<T: TryOrd, U: TryOrd, E> TryOrd for Lexicographic<Result<T, U>, E>
+where
+	T::Error: Into<E>,
+	U::Error: Into<E>,
+	T::OrdError: Into<E>,
+	U::OrdError: Into<E>
+{
+	fn try_cmp(&self, other: &Self) -> Result<Ordering, Self::OrdError> {
+		match (&self.0, &other.0) {
+			(Ok(lhs), Ok(rhs)) => lhs.try_cmp(rhs).map_err(Into::into),
+			(Err(lhs), Err(rhs)) => lhs.try_cmp(rhs).map_err(Into::into),
+			(lhs, rhs) => Ok(lhs.is_err().cmp(&rhs.is_err()))
+		}
+	}
+}
+
+macro_rules! impl_tuple {
+	($($name:ident $idx:tt)+) => {
+		impl<$($name: TryPartialEq,)+ E> TryPartialEq for Lexicographic<($($name,)+), E>
+		where
+			$($name::Error: Into<E>),+
+		{
+			type Error = E;
+
+			fn try_eq(&self, other: &Self) -> Result<bool, Self::Error> {
+				$(
+					if !(self.0).$idx.try_eq(&(other.0).$idx).map_err(Into::into)? {
+						return Ok(false);
+					}
+				)+
+
+				Ok(true)
+			}
+		}
+
+		impl<$($name: TryEq,)+ E> TryEq for Lexicographic<($($name,)+), E>
+		where
+			$($name::Error: Into<E>),+
+		{}
+
+		impl<$($name: TryPartialOrd,)+ E> TryPartialOrd for Lexicographic<($($name,)+), E>
+		where
+			$($name::Error: Into<E>, $name::OrdError: Into<E>),+
+		{
+			type OrdError = E;
+
+			fn try_partial_cmp(&self, other: &Self) -> Result<Option<Ordering>, Self::OrdError> {
+				$(
+					match (self.0).$idx.try_partial_cmp(&(other.0).$idx).map_err(Into::into)? {
+						Some(Ordering::Equal) => {},
+						ordering => return Ok(ordering)
+					}
+				)+
+
+				Ok(Some(Ordering::Equal))
+			}
+		}
+
+		impl<$($name: TryOrd,)+ E> TryOrd for Lexicographic<($($name,)+), E>
+		where
+			$($name::Error: Into<E>, $name::OrdError: Into<E>),+
+		{
+			fn try_cmp(&self, other: &Self) -> Result<Ordering, Self::OrdError> {
+				$(
+					match (self.0).$idx.try_cmp(&(other.0).$idx).map_err(Into::into)? {
+						Ordering::Equal => {},
+						ordering => return Ok(ordering)
+					}
+				)+
+
+				Ok(Ordering::Equal)
+			}
+		}
+	};
+}
+
+impl_tuple!(A 0);
+impl_tuple!(A 0 B 1);
+impl_tuple!(A 0 B 1 C 2);
+impl_tuple!(A 0 B 1 C 2 D 3);
+impl_tuple!(A 0 B 1 C 2 D 3 F 4);
+impl_tuple!(A 0 B 1 C 2 D 3 F 4 G 5);
+impl_tuple!(A 0 B 1 C 2 D 3 F 4 G 5 H 6);
+impl_tuple!(A 0 B 1 C 2 D 3 F 4 G 5 H 6 I 7);
+impl_tuple!(A 0 B 1 C 2 D 3 F 4 G 5 H 6 I 7 J 8);
+impl_tuple!(A 0 B 1 C 2 D 3 F 4 G 5 H 6 I 7 J 8 K 9);
+impl_tuple!(A 0 B 1 C 2 D 3 F 4 G 5 H 6 I 7 J 8 K 9 L 10);
+impl_tuple!(A 0 B 1 C 2 D 3 F 4 G 5 H 6 I 7 J 8 K 9 L 10 M 11);
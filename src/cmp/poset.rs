@@ -0,0 +1,151 @@
+//! Algorithms for partially ordered sets whose comparisons can fail.
+//!
+//! These only ever use [`TryPartialOrd::try_partial_cmp`], and as they're `no_std`, they write
+//! their results (which are indices into the input slice) into caller-provided buffers.
+
+use core::cmp::Ordering;
+use core::fmt;
+use super::TryPartialOrd;
+
+/// The error type used by the [`poset`](self) algorithms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PosetError<E> {
+	/// Comparing two elements failed.
+	Error(E),
+
+	/// An output (or scratch) buffer wasn't large enough.
+	BufferTooSmall,
+
+	/// The ordering contained a cycle.
+	///
+	/// This is also returned for inconsistent comparisons, such as `a < b` and `b < a`.
+	Cycle,
+}
+
+impl<E: fmt::Display> fmt::Display for PosetError<E> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Error(err) => fmt::Display::fmt(err, f),
+			Self::BufferTooSmall => write!(f, "output buffer is too small"),
+			Self::Cycle => write!(f, "ordering contains a cycle")
+		}
+	}
+}
+
+/// Returns whether `elements[lhs]` is strictly less than `elements[rhs]`.
+fn is_less<T: TryPartialOrd>(elements: &[T], lhs: usize, rhs: usize)
+	-> Result<bool, PosetError<T::OrdError>>
+{
+	elements[lhs].try_partial_cmp(&elements[rhs])
+		.map(|ord| ord == Some(Ordering::Less))
+		.map_err(PosetError::Error)
+}
+
+/// Writes the indices of elements for which `dominates(other, element)` is never true into `out`.
+fn extremal_elements<'o, T, F>(elements: &[T], out: &'o mut [usize], mut dominates: F)
+	-> Result<&'o mut [usize], PosetError<T::OrdError>>
+where
+	T: TryPartialOrd,
+	F: FnMut(usize, usize) -> Result<bool, PosetError<T::OrdError>>
+{
+	let mut len = 0;
+
+	'outer: for idx in 0..elements.len() {
+		for other in 0..elements.len() {
+			if other != idx && dominates(other, idx)? {
+				continue 'outer;
+			}
+		}
+
+		*out.get_mut(len).ok_or(PosetError::BufferTooSmall)? = idx;
+		len += 1;
+	}
+
+	Ok(&mut out[..len])
+}
+
+/// Finds the maximal elements of `elements`, ie the ones which no other element is greater than.
+///
+/// Their indices are written into `out` in ascending order, and the filled portion of `out` is
+/// returned.
+pub fn try_maximal_elements<'o, T: TryPartialOrd>(elements: &[T], out: &'o mut [usize])
+	-> Result<&'o mut [usize], PosetError<T::OrdError>>
+{
+	extremal_elements(elements, out, |other, idx| is_less(elements, idx, other))
+}
+
+/// Finds the minimal elements of `elements`, ie the ones which no other element is less than.
+///
+/// Their indices are written into `out` in ascending order, and the filled portion of `out` is
+/// returned.
+pub fn try_minimal_elements<'o, T: TryPartialOrd>(elements: &[T], out: &'o mut [usize])
+	-> Result<&'o mut [usize], PosetError<T::OrdError>>
+{
+	extremal_elements(elements, out, |other, idx| is_less(elements, other, idx))
+}
+
+/// Sorts `elements` topologically, so that every element comes after all the elements less than it.
+///
+/// The resulting permutation of indices is written into `out`, and the filled portion of `out` is
+/// returned. `scratch` is used to count how many unplaced elements are less than each element.
+/// Both must be at least as long as `elements`.
+///
+/// This uses Kahn's algorithm: at each step, out of the remaining elements that nothing remaining
+/// is less than, the one that came first in `elements` is placed next. Note this means that
+/// incomparable elements don't necessarily keep their relative order, as an element may have to
+/// wait for the elements less than it. It does `O(n²)` comparisons.
+///
+/// This returns [`PosetError::Cycle`] if there's no valid ordering (or if the comparisons are
+/// inconsistent).
+pub fn try_topological_sort<'o, T: TryPartialOrd>(
+	elements: &[T],
+	out: &'o mut [usize],
+	scratch: &mut [usize]
+) -> Result<&'o mut [usize], PosetError<T::OrdError>> {
+	let len = elements.len();
+	let out = out.get_mut(..len).ok_or(PosetError::BufferTooSmall)?;
+	let in_degrees = scratch.get_mut(..len).ok_or(PosetError::BufferTooSmall)?;
+
+	for idx in 0..len {
+		out[idx] = idx;
+		in_degrees[idx] = 0;
+
+		for other in 0..len {
+			if other != idx && is_less(elements, other, idx)? {
+				in_degrees[idx] += 1;
+			}
+		}
+	}
+
+	// `out[..sorted]` is the sorted portion, and `out[sorted..]` are the remaining elements, in
+	// their original order.
+	for sorted in 0..len {
+		let ready = out[sorted..].iter()
+			.position(|&idx| in_degrees[idx] == 0)
+			.ok_or(PosetError::Cycle)?;
+
+		out[sorted..=sorted + ready].rotate_right(1);
+
+		for remaining in sorted + 1..len {
+			if is_less(elements, out[sorted], out[remaining])? {
+				let in_degree = &mut in_degrees[out[remaining]];
+				*in_degree = in_degree.checked_sub(1).ok_or(PosetError::Cycle)?;
+			}
+		}
+	}
+
+	Ok(out)
+}
+
+/// Checks to see if every pair of elements in `elements` is comparable.
+pub fn try_is_chain<T: TryPartialOrd>(elements: &[T]) -> Result<bool, T::OrdError> {
+	for (idx, lhs) in elements.iter().enumerate() {
+		for rhs in &elements[idx + 1..] {
+			if lhs.try_partial_cmp(rhs)?.is_none() {
+				return Ok(false);
+			}
+		}
+	}
+
+	Ok(true)
+}
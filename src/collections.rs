@@ -0,0 +1,22 @@
+//! Collections whose keys are compared and hashed with the try traits.
+//!
+//! Other than [`TryArrayHeap`], these are only available with the `alloc` feature.
+mod binary_heap;
+#[cfg(feature = "alloc")]
+mod hash_map;
+#[cfg(feature = "alloc")]
+mod hash_set;
+#[cfg(feature = "alloc")]
+mod sorted_map;
+#[cfg(feature = "alloc")]
+mod sorted_vec;
+
+pub use binary_heap::*;
+#[cfg(feature = "alloc")]
+pub use hash_map::*;
+#[cfg(feature = "alloc")]
+pub use hash_set::*;
+#[cfg(feature = "alloc")]
+pub use sorted_map::*;
+#[cfg(feature = "alloc")]
+pub use sorted_vec::*;
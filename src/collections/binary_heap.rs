@@ -0,0 +1,406 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+use crate::cmp::TryOrd;
+
+// All comparisons for an operation are done before the heap is modified: first the position an
+// element needs to move to is found, and only then are the elements moved. This means a failing
+// comparison always leaves the heap exactly as it was.
+
+/// Finds the position the element at `pos` needs to be sifted up to.
+fn sift_up_target<S, T, F>(data: &[S], get: F, mut pos: usize) -> Result<usize, T::OrdError>
+where
+	T: TryOrd,
+	F: Fn(&S) -> &T
+{
+	let item = get(&data[pos]);
+
+	while pos > 0 {
+		let parent = (pos - 1) / 2;
+
+		if item.try_le(get(&data[parent]))? {
+			break;
+		}
+
+		pos = parent;
+	}
+
+	Ok(pos)
+}
+
+/// Moves the element at `pos` up to its ancestor `target`, moving the elements between down.
+fn move_up<S>(data: &mut [S], mut pos: usize, target: usize) {
+	while pos != target {
+		let parent = (pos - 1) / 2;
+		data.swap(pos, parent);
+		pos = parent;
+	}
+}
+
+/// Pushes the last element of `data[..=last]` onto the heap `data[..last]`.
+fn try_push_last<S, T, F>(data: &mut [S], get: F, last: usize) -> Result<(), T::OrdError>
+where
+	T: TryOrd,
+	F: Fn(&S) -> &T
+{
+	let target = sift_up_target(data, get, last)?;
+	move_up(data, last, target);
+
+	Ok(())
+}
+
+/// Moves the largest element of the heap `data[..=last]` to `last`, leaving `data[..last]` a
+/// valid heap.
+fn try_pop_to_last<S, T, F>(data: &mut [S], get: F, last: usize) -> Result<(), T::OrdError>
+where
+	T: TryOrd,
+	F: Fn(&S) -> &T
+{
+	// The element at `last` replaces the root, and is then sifted down. The path it takes is
+	// recorded as one bit per level (whether the right child was taken); as the heap can have at
+	// most `usize::BITS` levels, this always fits.
+	let item = get(&data[last]);
+	let (mut path, mut depth, mut pos) = (0u128, 0, 0);
+
+	loop {
+		let mut child = 2 * pos + 1;
+
+		if child >= last {
+			break;
+		}
+
+		if child + 1 < last && get(&data[child]).try_le(get(&data[child + 1]))? {
+			child += 1;
+		}
+
+		if item.try_ge(get(&data[child]))? {
+			break;
+		}
+
+		path |= ((child - (2 * pos + 1)) as u128) << depth;
+		depth += 1;
+		pos = child;
+	}
+
+	data.swap(0, last);
+	pos = 0;
+
+	for level in 0..depth {
+		let child = 2 * pos + 1 + ((path >> level) & 1) as usize;
+		data.swap(pos, child);
+		pos = child;
+	}
+
+	Ok(())
+}
+
+/// A priority queue (max-heap) whose elements are compared with [`TryOrd`].
+///
+/// If a comparison fails during [`try_push`](Self::try_push) or [`try_pop`](Self::try_pop), the
+/// heap is left exactly as it was before the call, so the heap invariant always holds.
+///
+/// This is only available with the `alloc` feature; see [`TryArrayHeap`] for a fixed-capacity
+/// version that's always available.
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+pub struct TryBinaryHeap<T> {
+	data: Vec<T>
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Default for TryBinaryHeap<T> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> TryBinaryHeap<T> {
+	/// Creates an empty heap.
+	#[inline]
+	pub const fn new() -> Self {
+		Self { data: Vec::new() }
+	}
+
+	/// Creates an empty heap with space for at least `capacity` elements.
+	#[inline]
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self { data: Vec::with_capacity(capacity) }
+	}
+
+	/// Returns the number of elements in the heap.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.data.len()
+	}
+
+	/// Returns whether the heap is empty.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.data.is_empty()
+	}
+
+	/// Returns the greatest element in the heap.
+	#[inline]
+	pub fn peek(&self) -> Option<&T> {
+		self.data.first()
+	}
+
+	/// Returns an iterator over the elements of the heap, in an arbitrary order.
+	#[inline]
+	pub fn iter(&self) -> core::slice::Iter<'_, T> {
+		self.data.iter()
+	}
+
+	/// Removes all elements from the heap.
+	#[inline]
+	pub fn clear(&mut self) {
+		self.data.clear();
+	}
+
+	/// Returns the underlying vector, in an arbitrary order.
+	#[inline]
+	pub fn into_vec(self) -> Vec<T> {
+		self.data
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T: TryOrd> TryBinaryHeap<T> {
+	/// The fallible equivalent of `BinaryHeap::push`.
+	///
+	/// If a comparison fails, `item` is dropped and the heap is unchanged.
+	pub fn try_push(&mut self, item: T) -> Result<(), T::OrdError> {
+		self.data.push(item);
+		let last = self.data.len() - 1;
+
+		if let Err(err) = try_push_last(&mut self.data, |item| item, last) {
+			self.data.pop();
+			return Err(err);
+		}
+
+		Ok(())
+	}
+
+	/// The fallible equivalent of `BinaryHeap::pop`.
+	///
+	/// If a comparison fails, the heap is unchanged.
+	pub fn try_pop(&mut self) -> Result<Option<T>, T::OrdError> {
+		let last = match self.data.len() {
+			0 => return Ok(None),
+			len => len - 1
+		};
+
+		try_pop_to_last(&mut self.data, |item| item, last)?;
+		Ok(self.data.pop())
+	}
+
+	/// The fallible equivalent of `BinaryHeap::into_sorted_vec`, which returns the elements in
+	/// ascending order.
+	///
+	/// If a comparison fails, the elements are dropped.
+	pub fn try_into_sorted_vec(mut self) -> Result<Vec<T>, T::OrdError> {
+		for last in (1..self.data.len()).rev() {
+			try_pop_to_last(&mut self.data, |item| item, last)?;
+		}
+
+		Ok(self.data)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T: fmt::Debug> fmt::Debug for TryBinaryHeap<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_list().entries(self.iter()).finish()
+	}
+}
+
+/// The error returned by [`TryArrayHeap::try_push`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PushError<T, E> {
+	/// The heap was full; the item that was being pushed is returned.
+	Full(T),
+
+	/// Comparing the elements failed.
+	Error(E),
+}
+
+impl<T, E: fmt::Display> fmt::Display for PushError<T, E> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Full(_) => write!(f, "heap is full"),
+			Self::Error(err) => fmt::Display::fmt(err, f)
+		}
+	}
+}
+
+/// A fixed-capacity version of [`TryBinaryHeap`] which doesn't need to allocate.
+///
+/// It has the same guarantees as `TryBinaryHeap` when comparisons fail.
+pub struct TryArrayHeap<T, const N: usize> {
+	data: [Option<T>; N],
+	len: usize
+}
+
+/// Gets the element out of an occupied slot of a [`TryArrayHeap`].
+fn occupied<T>(slot: &Option<T>) -> &T {
+	slot.as_ref().expect("slots within the heap are always occupied")
+}
+
+impl<T, const N: usize> Default for TryArrayHeap<T, N> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T, const N: usize> TryArrayHeap<T, N> {
+	/// Creates an empty heap.
+	#[inline]
+	pub fn new() -> Self {
+		Self { data: [(); N].map(|_| None), len: 0 }
+	}
+
+	/// Returns the number of elements in the heap.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Returns whether the heap is empty.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Returns whether the heap is full.
+	#[inline]
+	pub fn is_full(&self) -> bool {
+		self.len == N
+	}
+
+	/// Returns the maximum number of elements the heap can hold.
+	#[inline]
+	pub const fn capacity(&self) -> usize {
+		N
+	}
+
+	/// Returns the greatest element in the heap.
+	#[inline]
+	pub fn peek(&self) -> Option<&T> {
+		self.data.first().and_then(Option::as_ref)
+	}
+
+	/// Returns an iterator over the elements of the heap, in an arbitrary order.
+	pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+		self.data[..self.len].iter().map(occupied)
+	}
+
+	/// Removes all elements from the heap.
+	pub fn clear(&mut self) {
+		for slot in &mut self.data[..self.len] {
+			*slot = None;
+		}
+
+		self.len = 0;
+	}
+}
+
+impl<T: TryOrd, const N: usize> TryArrayHeap<T, N> {
+	/// The fallible equivalent of `BinaryHeap::push`.
+	///
+	/// If the heap is full, `item` is returned. If a comparison fails, `item` is dropped and the
+	/// heap is unchanged.
+	pub fn try_push(&mut self, item: T) -> Result<(), PushError<T, T::OrdError>> {
+		if self.is_full() {
+			return Err(PushError::Full(item));
+		}
+
+		self.data[self.len] = Some(item);
+
+		match try_push_last(&mut self.data, occupied, self.len) {
+			Ok(()) => {
+				self.len += 1;
+				Ok(())
+			},
+			Err(err) => {
+				self.data[self.len] = None;
+				Err(PushError::Error(err))
+			}
+		}
+	}
+
+	/// The fallible equivalent of `BinaryHeap::pop`.
+	///
+	/// If a comparison fails, the heap is unchanged.
+	pub fn try_pop(&mut self) -> Result<Option<T>, T::OrdError> {
+		if self.is_empty() {
+			return Ok(None);
+		}
+
+		try_pop_to_last(&mut self.data, occupied, self.len - 1)?;
+		self.len -= 1;
+
+		Ok(self.data[self.len].take())
+	}
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for TryArrayHeap<T, N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_list().entries(self.iter()).finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::vec::Vec;
+	use crate::testing::{fallible, retry, values, Budget, Fallible};
+	use super::*;
+
+	const VALUES: [u32; 12] = [5, 12, 1, 9, 9, 3, 7, 0, 11, 4, 8, 2];
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn failures_leave_the_heap_unchanged() {
+		let budget = Budget::new(usize::MAX);
+		let mut heap = TryBinaryHeap::new();
+
+		for item in fallible(&VALUES, &budget) {
+			heap.try_push(item).unwrap();
+		}
+
+		let snapshot = |heap: &TryBinaryHeap<Fallible>| values(heap.iter());
+		let item = Fallible::new(10, &budget);
+		retry(&mut heap, &budget, snapshot, |heap| heap.try_push(item));
+		let popped = retry(&mut heap, &budget, snapshot, |heap| heap.try_pop());
+		assert_eq!(popped.map(|item| item.value), Some(12));
+
+		let sorted = heap.try_into_sorted_vec().unwrap();
+		assert_eq!(values(&sorted), [0, 1, 2, 3, 4, 5, 7, 8, 9, 9, 10, 11]);
+	}
+
+	#[test]
+	fn failures_leave_the_array_heap_unchanged() {
+		let budget = Budget::new(usize::MAX);
+		let mut heap = TryArrayHeap::<_, 16>::new();
+
+		for item in fallible(&VALUES, &budget) {
+			heap.try_push(item).unwrap();
+		}
+
+		let snapshot = |heap: &TryArrayHeap<Fallible, 16>| values(heap.iter());
+		let item = Fallible::new(10, &budget);
+		retry(&mut heap, &budget, snapshot, |heap| heap.try_push(item));
+		let popped = retry(&mut heap, &budget, snapshot, |heap| heap.try_pop());
+		assert_eq!(popped.map(|item| item.value), Some(12));
+
+		let mut sorted = Vec::new();
+
+		while let Some(item) = heap.try_pop().unwrap() {
+			sorted.push(item.value);
+		}
+
+		assert_eq!(sorted, [11, 10, 9, 9, 8, 7, 5, 4, 3, 2, 1, 0]);
+	}
+}
@@ -0,0 +1,309 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::mem;
+use crate::cmp::{TryPartialEq, TryEq};
+use crate::hash::{HashError, TryBuildHasher, TryHash};
+
+/// The error returned when hashing or comparing a key fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyError<H, E, B> {
+	/// Hashing the key failed.
+	Hash(H),
+
+	/// Comparing the key with another key failed.
+	Eq(E),
+
+	/// Building the hasher failed.
+	BuildHasher(B),
+}
+
+impl<H: fmt::Display, E: fmt::Display, B: fmt::Display> fmt::Display for KeyError<H, E, B> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Hash(err) => fmt::Display::fmt(err, f),
+			Self::Eq(err) => fmt::Display::fmt(err, f),
+			Self::BuildHasher(err) => fmt::Display::fmt(err, f)
+		}
+	}
+}
+
+impl<H, E, B> From<HashError<H, B>> for KeyError<H, E, B> {
+	#[inline]
+	fn from(err: HashError<H, B>) -> Self {
+		match err {
+			HashError::Hash(err) => Self::Hash(err),
+			HashError::Hasher(err) => Self::BuildHasher(err)
+		}
+	}
+}
+
+/// The [`KeyError`] used by [`TryHashMap`] and [`TryHashSet`](super::TryHashSet).
+pub type MapKeyError<K, S> = KeyError<
+	<K as TryHash>::Error,
+	<K as TryPartialEq>::Error,
+	<S as TryBuildHasher>::Error
+>;
+
+struct Entry<K, V> {
+	hash: u64,
+	key: K,
+	value: V
+}
+
+/// The bucket index and the index within that bucket of an entry.
+type Location = (usize, usize);
+
+/// The smallest number of buckets that's allocated.
+const MIN_BUCKETS: usize = 8;
+
+/// A hash map whose keys are hashed with [`TryHash`] and compared with [`TryEq`].
+///
+/// Every operation that needs to hash or compare keys returns a [`Result`]. Hashes are computed
+/// and all comparisons are done before the map is modified, so a failure never leaves the map in
+/// an inconsistent state. (Hashes are also cached, so growing the map never fails.)
+pub struct TryHashMap<K, V, S> {
+	buckets: Vec<Vec<Entry<K, V>>>,
+	len: usize,
+	hash_builder: S
+}
+
+impl<K, V, S: Default> TryHashMap<K, V, S> {
+	/// Creates an empty map.
+	#[inline]
+	pub fn new() -> Self {
+		Self::with_hasher(S::default())
+	}
+}
+
+impl<K, V, S: Default> Default for TryHashMap<K, V, S> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<K, V, S> TryHashMap<K, V, S> {
+	/// Creates an empty map which will use `hash_builder` to hash keys.
+	#[inline]
+	pub const fn with_hasher(hash_builder: S) -> Self {
+		Self { buckets: Vec::new(), len: 0, hash_builder }
+	}
+
+	/// Returns the hasher builder used by the map.
+	#[inline]
+	pub fn hasher(&self) -> &S {
+		&self.hash_builder
+	}
+
+	/// Returns the number of elements in the map.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Returns whether the map is empty.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Removes all elements from the map.
+	pub fn clear(&mut self) {
+		self.buckets.clear();
+		self.len = 0;
+	}
+
+	/// Returns an iterator over the entries of the map, in an arbitrary order.
+	pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+		self.buckets.iter().flatten().map(|entry| (&entry.key, &entry.value))
+	}
+
+	/// Returns an iterator over the entries of the map with mutable values, in an arbitrary order.
+	pub fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &mut V)> + '_ {
+		self.buckets.iter_mut().flatten().map(|entry| (&entry.key, &mut entry.value))
+	}
+
+	/// Returns an iterator over the keys of the map, in an arbitrary order.
+	pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+		self.iter().map(|(key, _)| key)
+	}
+
+	/// Returns an iterator over the values of the map, in an arbitrary order.
+	pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+		self.iter().map(|(_, value)| value)
+	}
+
+	fn bucket_index(&self, hash: u64) -> usize {
+		(hash % self.buckets.len() as u64) as usize
+	}
+
+	/// Grows the map (if needed) so that another element can be inserted.
+	fn reserve_one(&mut self) {
+		if self.len < self.buckets.len() / 4 * 3 {
+			return;
+		}
+
+		let new_len = (self.buckets.len() * 2).max(MIN_BUCKETS);
+		let old_buckets = mem::replace(&mut self.buckets, Vec::with_capacity(new_len));
+		self.buckets.resize_with(new_len, Vec::new);
+
+		for entry in old_buckets.into_iter().flatten() {
+			let idx = self.bucket_index(entry.hash);
+			self.buckets[idx].push(entry);
+		}
+	}
+}
+
+impl<K: TryHash + TryEq, V, S: TryBuildHasher> TryHashMap<K, V, S> {
+	fn hash(&self, key: &K) -> Result<u64, MapKeyError<K, S>> {
+		Ok(self.hash_builder.try_hash_one(key)?)
+	}
+
+	/// Returns the hash of `key`, and its location if it's in the map.
+	fn find(&self, key: &K) -> Result<(u64, Option<Location>), MapKeyError<K, S>> {
+		let hash = self.hash(key)?;
+
+		if self.buckets.is_empty() {
+			return Ok((hash, None));
+		}
+
+		let bucket_idx = self.bucket_index(hash);
+
+		for (idx, entry) in self.buckets[bucket_idx].iter().enumerate() {
+			if entry.hash == hash && entry.key.try_eq(key).map_err(KeyError::Eq)? {
+				return Ok((hash, Some((bucket_idx, idx))));
+			}
+		}
+
+		Ok((hash, None))
+	}
+
+	/// The fallible equivalent of `HashMap::insert`.
+	///
+	/// If the map already had `key`, its value is replaced and the old value is returned (the key
+	/// itself isn't updated).
+	pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, MapKeyError<K, S>> {
+		let (hash, found) = self.find(&key)?;
+
+		if let Some((bucket_idx, idx)) = found {
+			return Ok(Some(mem::replace(&mut self.buckets[bucket_idx][idx].value, value)));
+		}
+
+		self.insert_unique(hash, key, value);
+		Ok(None)
+	}
+
+	/// Inserts `key` if it isn't already in the map, returning whether it was inserted. Unlike
+	/// calling [`try_contains_key`](Self::try_contains_key) and then
+	/// [`try_insert`](Self::try_insert), this only hashes and compares `key` once.
+	pub(super) fn try_insert_new(&mut self, key: K, value: V) -> Result<bool, MapKeyError<K, S>> {
+		let (hash, found) = self.find(&key)?;
+
+		if found.is_some() {
+			return Ok(false);
+		}
+
+		self.insert_unique(hash, key, value);
+		Ok(true)
+	}
+
+	/// Inserts `key`, which has the hash `hash` and isn't already in the map.
+	fn insert_unique(&mut self, hash: u64, key: K, value: V) {
+		self.reserve_one();
+		let bucket_idx = self.bucket_index(hash);
+		self.buckets[bucket_idx].push(Entry { hash, key, value });
+		self.len += 1;
+	}
+
+	/// The fallible equivalent of `HashMap::get`.
+	pub fn try_get(&self, key: &K) -> Result<Option<&V>, MapKeyError<K, S>> {
+		Ok(self.find(key)?.1.map(|(bucket_idx, idx)| &self.buckets[bucket_idx][idx].value))
+	}
+
+	/// The fallible equivalent of `HashMap::get_key_value`.
+	pub fn try_get_key_value(&self, key: &K) -> Result<Option<(&K, &V)>, MapKeyError<K, S>> {
+		Ok(self.find(key)?.1.map(|(bucket_idx, idx)| {
+			let entry = &self.buckets[bucket_idx][idx];
+			(&entry.key, &entry.value)
+		}))
+	}
+
+	/// The fallible equivalent of `HashMap::get_mut`.
+	pub fn try_get_mut(&mut self, key: &K) -> Result<Option<&mut V>, MapKeyError<K, S>> {
+		match self.find(key)?.1 {
+			Some((bucket_idx, idx)) => Ok(Some(&mut self.buckets[bucket_idx][idx].value)),
+			None => Ok(None)
+		}
+	}
+
+	/// The fallible equivalent of `HashMap::contains_key`.
+	pub fn try_contains_key(&self, key: &K) -> Result<bool, MapKeyError<K, S>> {
+		Ok(self.find(key)?.1.is_some())
+	}
+
+	/// The fallible equivalent of `HashMap::remove`.
+	pub fn try_remove(&mut self, key: &K) -> Result<Option<V>, MapKeyError<K, S>> {
+		Ok(self.try_remove_entry(key)?.map(|(_, value)| value))
+	}
+
+	/// The fallible equivalent of `HashMap::remove_entry`.
+	pub fn try_remove_entry(&mut self, key: &K) -> Result<Option<(K, V)>, MapKeyError<K, S>> {
+		match self.find(key)?.1 {
+			Some((bucket_idx, idx)) => {
+				let entry = self.buckets[bucket_idx].swap_remove(idx);
+				self.len -= 1;
+				Ok(Some((entry.key, entry.value)))
+			},
+			None => Ok(None)
+		}
+	}
+}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for TryHashMap<K, V, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_map().entries(self.iter()).finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::hash::BuildHasherDefault;
+	use std::collections::hash_map::DefaultHasher;
+	use std::vec::Vec;
+	use crate::testing::{retry, Budget, Fallible};
+	use super::TryHashMap;
+
+	type Map<'b> = TryHashMap<Fallible<'b>, u32, BuildHasherDefault<DefaultHasher>>;
+
+	/// Returns the entries of `map`, sorted by key.
+	fn entries(map: &Map) -> Vec<(u32, u32)> {
+		let mut entries: Vec<_> = map.iter().map(|(key, &value)| (key.value, value)).collect();
+		entries.sort_unstable();
+		entries
+	}
+
+	#[test]
+	fn failures_leave_the_map_unchanged() {
+		let budget = Budget::new(usize::MAX);
+		let mut map = Map::new();
+
+		for key in 0..24 {
+			map.try_insert(Fallible::new(key, &budget), key * key).unwrap();
+		}
+
+		// this insertion makes the map grow.
+		let key = Fallible::new(24, &budget);
+		assert_eq!(retry(&mut map, &budget, entries, |map| map.try_insert(key, 0)), None);
+
+		let key = Fallible::new(3, &budget);
+		assert_eq!(retry(&mut map, &budget, entries, |map| map.try_insert(key, 0)), Some(9));
+		assert_eq!(retry(&mut map, &budget, entries, |map| map.try_remove(&key)), Some(0));
+
+		assert_eq!(map.len(), 24);
+
+		for key in (0..25).filter(|&key| key != 3) {
+			let expected = if key == 24 { 0 } else { key * key };
+			assert_eq!(map.try_get(&Fallible::new(key, &budget)), Ok(Some(&expected)));
+		}
+	}
+}
@@ -0,0 +1,126 @@
+use core::fmt;
+use crate::cmp::TryEq;
+use crate::hash::{TryBuildHasher, TryHash};
+use super::{MapKeyError, TryHashMap};
+
+/// A hash set whose elements are hashed with [`TryHash`] and compared with [`TryEq`].
+///
+/// This is a [`TryHashMap`] with `()` values, and has the same guarantees about failures.
+pub struct TryHashSet<K, S> {
+	map: TryHashMap<K, (), S>
+}
+
+impl<K, S: Default> TryHashSet<K, S> {
+	/// Creates an empty set.
+	#[inline]
+	pub fn new() -> Self {
+		Self { map: TryHashMap::new() }
+	}
+}
+
+impl<K, S: Default> Default for TryHashSet<K, S> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<K, S> TryHashSet<K, S> {
+	/// Creates an empty set which will use `hash_builder` to hash elements.
+	#[inline]
+	pub const fn with_hasher(hash_builder: S) -> Self {
+		Self { map: TryHashMap::with_hasher(hash_builder) }
+	}
+
+	/// Returns the hasher builder used by the set.
+	#[inline]
+	pub fn hasher(&self) -> &S {
+		self.map.hasher()
+	}
+
+	/// Returns the number of elements in the set.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	/// Returns whether the set is empty.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+
+	/// Removes all elements from the set.
+	#[inline]
+	pub fn clear(&mut self) {
+		self.map.clear();
+	}
+
+	/// Returns an iterator over the elements of the set, in an arbitrary order.
+	pub fn iter(&self) -> impl Iterator<Item = &K> + '_ {
+		self.map.keys()
+	}
+}
+
+impl<K: TryHash + TryEq, S: TryBuildHasher> TryHashSet<K, S> {
+	/// The fallible equivalent of `HashSet::insert`.
+	///
+	/// Returns whether `value` was newly inserted; if it wasn't, the set isn't modified.
+	#[inline]
+	pub fn try_insert(&mut self, value: K) -> Result<bool, MapKeyError<K, S>> {
+		self.map.try_insert_new(value, ())
+	}
+
+	/// The fallible equivalent of `HashSet::contains`.
+	#[inline]
+	pub fn try_contains(&self, value: &K) -> Result<bool, MapKeyError<K, S>> {
+		self.map.try_contains_key(value)
+	}
+
+	/// The fallible equivalent of `HashSet::get`.
+	pub fn try_get(&self, value: &K) -> Result<Option<&K>, MapKeyError<K, S>> {
+		Ok(self.map.try_get_key_value(value)?.map(|(key, _)| key))
+	}
+
+	/// The fallible equivalent of `HashSet::remove`.
+	#[inline]
+	pub fn try_remove(&mut self, value: &K) -> Result<bool, MapKeyError<K, S>> {
+		Ok(self.map.try_remove(value)?.is_some())
+	}
+
+	/// The fallible equivalent of `HashSet::take`.
+	pub fn try_take(&mut self, value: &K) -> Result<Option<K>, MapKeyError<K, S>> {
+		Ok(self.map.try_remove_entry(value)?.map(|(key, _)| key))
+	}
+}
+
+impl<K: fmt::Debug, S> fmt::Debug for TryHashSet<K, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_set().entries(self.iter()).finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use core::hash::BuildHasherDefault;
+	use std::collections::hash_map::DefaultHasher;
+	use crate::testing::{Budget, Fallible};
+	use super::TryHashSet;
+
+	#[test]
+	fn insert_hashes_once() {
+		let budget = Budget::new(usize::MAX);
+		let mut set = TryHashSet::<_, BuildHasherDefault<DefaultHasher>>::new();
+
+		for value in 0..16 {
+			set.try_insert(Fallible::new(value, &budget)).unwrap();
+		}
+
+		// a new value only needs to be hashed, and an existing one hashed and compared once.
+		budget.set(1);
+		assert_eq!(set.try_insert(Fallible::new(16, &budget)), Ok(true));
+		budget.set(2);
+		assert_eq!(set.try_insert(Fallible::new(5, &budget)), Ok(false));
+		assert_eq!(set.len(), 17);
+	}
+}
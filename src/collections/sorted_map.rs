@@ -0,0 +1,229 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::mem;
+use core::ops::{Bound, RangeBounds};
+use crate::cmp::TryOrd;
+use crate::slice::TrySliceExt;
+
+/// An ordered map whose keys are compared with [`TryOrd`], with an API modeled after `BTreeMap`.
+///
+/// All comparisons for an operation are done before the map is modified, so a comparison failing
+/// never leaves it in an inconsistent state.
+///
+/// It's backed by a vector of entries sorted by key, so lookups are `O(log n)`, but insertions
+/// and removals (including [`pop_first`](Self::pop_first)) are `O(n)`. This makes it best suited
+/// to small maps, or ones that are mostly read.
+#[derive(Clone)]
+pub struct TrySortedMap<K, V> {
+	entries: Vec<(K, V)>
+}
+
+impl<K, V> Default for TrySortedMap<K, V> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<K, V> TrySortedMap<K, V> {
+	/// Creates an empty map.
+	#[inline]
+	pub const fn new() -> Self {
+		Self { entries: Vec::new() }
+	}
+
+	/// Returns the number of elements in the map.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	/// Returns whether the map is empty.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// Removes all elements from the map.
+	#[inline]
+	pub fn clear(&mut self) {
+		self.entries.clear();
+	}
+
+	/// Returns an iterator over the entries of the map, sorted by key.
+	pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> + ExactSizeIterator + '_ {
+		self.entries.iter().map(|(key, value)| (key, value))
+	}
+
+	/// Returns an iterator over the entries of the map with mutable values, sorted by key.
+	pub fn iter_mut(&mut self)
+		-> impl DoubleEndedIterator<Item = (&K, &mut V)> + ExactSizeIterator + '_
+	{
+		self.entries.iter_mut().map(|(key, value)| (&*key, value))
+	}
+
+	/// Returns an iterator over the keys of the map, in sorted order.
+	pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator + '_ {
+		self.entries.iter().map(|(key, _)| key)
+	}
+
+	/// Returns an iterator over the values of the map, sorted by key.
+	pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> + ExactSizeIterator + '_ {
+		self.entries.iter().map(|(_, value)| value)
+	}
+
+	/// Returns the entry with the smallest key.
+	#[inline]
+	pub fn first_key_value(&self) -> Option<(&K, &V)> {
+		self.entries.first().map(|(key, value)| (key, value))
+	}
+
+	/// Returns the entry with the largest key.
+	#[inline]
+	pub fn last_key_value(&self) -> Option<(&K, &V)> {
+		self.entries.last().map(|(key, value)| (key, value))
+	}
+
+	/// Removes and returns the entry with the smallest key.
+	#[inline]
+	pub fn pop_first(&mut self) -> Option<(K, V)> {
+		if self.entries.is_empty() {
+			None
+		} else {
+			Some(self.entries.remove(0))
+		}
+	}
+
+	/// Removes and returns the entry with the largest key.
+	#[inline]
+	pub fn pop_last(&mut self) -> Option<(K, V)> {
+		self.entries.pop()
+	}
+}
+
+impl<K: TryOrd, V> TrySortedMap<K, V> {
+	fn search(&self, key: &K) -> Result<Result<usize, usize>, K::OrdError> {
+		self.entries.try_binary_search_by(|(other, _)| other.try_cmp(key))
+	}
+
+	/// Returns the index of the first entry whose key is greater than or equal to `bound` (or, if
+	/// `skip_equal` is true, strictly greater than it).
+	fn index_after(&self, bound: &K, skip_equal: bool) -> Result<usize, K::OrdError> {
+		self.entries.try_partition_point(|(key, _)| {
+			key.try_cmp(bound)
+				.map(|ord| ord == Ordering::Less || (skip_equal && ord == Ordering::Equal))
+		})
+	}
+
+	/// The fallible equivalent of `BTreeMap::insert`.
+	///
+	/// If the map already had `key`, its value is replaced and the old value is returned (the key
+	/// itself isn't updated).
+	pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, K::OrdError> {
+		match self.search(&key)? {
+			Ok(index) => Ok(Some(mem::replace(&mut self.entries[index].1, value))),
+			Err(index) => {
+				self.entries.insert(index, (key, value));
+				Ok(None)
+			}
+		}
+	}
+
+	/// The fallible equivalent of `BTreeMap::get`.
+	pub fn try_get(&self, key: &K) -> Result<Option<&V>, K::OrdError> {
+		Ok(self.search(key)?.ok().map(|index| &self.entries[index].1))
+	}
+
+	/// The fallible equivalent of `BTreeMap::get_key_value`.
+	pub fn try_get_key_value(&self, key: &K) -> Result<Option<(&K, &V)>, K::OrdError> {
+		Ok(self.search(key)?.ok().map(|index| {
+			let (key, value) = &self.entries[index];
+			(key, value)
+		}))
+	}
+
+	/// The fallible equivalent of `BTreeMap::get_mut`.
+	pub fn try_get_mut(&mut self, key: &K) -> Result<Option<&mut V>, K::OrdError> {
+		match self.search(key)? {
+			Ok(index) => Ok(Some(&mut self.entries[index].1)),
+			Err(_) => Ok(None)
+		}
+	}
+
+	/// The fallible equivalent of `BTreeMap::contains_key`.
+	pub fn try_contains_key(&self, key: &K) -> Result<bool, K::OrdError> {
+		Ok(self.search(key)?.is_ok())
+	}
+
+	/// The fallible equivalent of `BTreeMap::remove`.
+	pub fn try_remove(&mut self, key: &K) -> Result<Option<V>, K::OrdError> {
+		Ok(self.try_remove_entry(key)?.map(|(_, value)| value))
+	}
+
+	/// The fallible equivalent of `BTreeMap::remove_entry`.
+	pub fn try_remove_entry(&mut self, key: &K) -> Result<Option<(K, V)>, K::OrdError> {
+		Ok(self.search(key)?.ok().map(|index| self.entries.remove(index)))
+	}
+
+	/// The fallible equivalent of `BTreeMap::range`.
+	///
+	/// Unlike `BTreeMap::range`, this doesn't panic if the start of `range` is after its end;
+	/// instead, the returned iterator is empty.
+	pub fn try_range<R: RangeBounds<K>>(&self, range: R)
+		-> Result<impl DoubleEndedIterator<Item = (&K, &V)> + ExactSizeIterator + '_, K::OrdError>
+	{
+		let start = match range.start_bound() {
+			Bound::Included(start) => self.index_after(start, false)?,
+			Bound::Excluded(start) => self.index_after(start, true)?,
+			Bound::Unbounded => 0
+		};
+
+		let end = match range.end_bound() {
+			Bound::Included(end) => self.index_after(end, true)?,
+			Bound::Excluded(end) => self.index_after(end, false)?,
+			Bound::Unbounded => self.entries.len()
+		}.max(start);
+
+		Ok(self.entries[start..end].iter().map(|(key, value)| (key, value)))
+	}
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for TrySortedMap<K, V> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_map().entries(self.iter()).finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::vec::Vec;
+	use crate::testing::{retry, Budget, Fallible};
+	use super::TrySortedMap;
+
+	fn entries(map: &TrySortedMap<Fallible, u32>) -> Vec<(u32, u32)> {
+		map.iter().map(|(key, &value)| (key.value, value)).collect()
+	}
+
+	#[test]
+	fn failures_leave_the_map_unchanged() {
+		let budget = Budget::new(usize::MAX);
+		let mut map = TrySortedMap::new();
+
+		for key in (0..20).rev() {
+			map.try_insert(Fallible::new(key * 2, &budget), key).unwrap();
+		}
+
+		let key = Fallible::new(7, &budget);
+		assert_eq!(retry(&mut map, &budget, entries, |map| map.try_insert(key, 100)), None);
+
+		let key = Fallible::new(8, &budget);
+		assert_eq!(retry(&mut map, &budget, entries, |map| map.try_insert(key, 100)), Some(4));
+		assert_eq!(retry(&mut map, &budget, entries, |map| map.try_remove(&key)), Some(100));
+
+		let mut expected: Vec<_> = (0..20).map(|key| (key * 2, key)).collect();
+		expected.insert(4, (7, 100));
+		expected.remove(5);
+		assert_eq!(entries(&map), expected);
+	}
+}
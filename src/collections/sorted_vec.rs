@@ -0,0 +1,166 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use crate::cmp::TryOrd;
+use crate::slice::TrySliceExt;
+
+/// A vector that's kept sorted using [`TryOrd`].
+///
+/// All comparisons for an operation are done before the vector is modified, so a comparison
+/// failing never leaves it unsorted (or missing elements).
+#[derive(Debug, Clone)]
+pub struct TrySortedVec<T> {
+	elements: Vec<T>
+}
+
+impl<T> Default for TrySortedVec<T> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> TrySortedVec<T> {
+	/// Creates an empty vector.
+	#[inline]
+	pub const fn new() -> Self {
+		Self { elements: Vec::new() }
+	}
+
+	/// Creates an empty vector with space for at least `capacity` elements.
+	#[inline]
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self { elements: Vec::with_capacity(capacity) }
+	}
+
+	/// Returns the number of elements in the vector.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.elements.len()
+	}
+
+	/// Returns whether the vector is empty.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.elements.is_empty()
+	}
+
+	/// Returns the elements, in sorted order.
+	#[inline]
+	pub fn as_slice(&self) -> &[T] {
+		&self.elements
+	}
+
+	/// Returns an iterator over the elements, in sorted order.
+	#[inline]
+	pub fn iter(&self) -> core::slice::Iter<'_, T> {
+		self.elements.iter()
+	}
+
+	/// Removes all elements from the vector.
+	#[inline]
+	pub fn clear(&mut self) {
+		self.elements.clear();
+	}
+
+	/// Removes and returns the element at `index`.
+	///
+	/// # Panics
+	///
+	/// Panics if `index` is out of bounds.
+	#[inline]
+	pub fn remove(&mut self, index: usize) -> T {
+		self.elements.remove(index)
+	}
+
+	/// Returns the underlying vector.
+	#[inline]
+	pub fn into_vec(self) -> Vec<T> {
+		self.elements
+	}
+}
+
+impl<T: TryOrd> TrySortedVec<T> {
+	/// Searches for `value`, returning `Ok(index)` if it's found, and `Err(index)` with where it
+	/// could be inserted if it's not. (See [`slice::binary_search`].)
+	pub fn try_binary_search(&self, value: &T) -> Result<Result<usize, usize>, T::OrdError> {
+		self.elements.try_binary_search(value)
+	}
+
+	/// Inserts `value`, returning the index it was inserted at.
+	///
+	/// If there are elements equal to `value`, it's inserted after them.
+	pub fn try_insert(&mut self, value: T) -> Result<usize, T::OrdError> {
+		let index = self.elements.try_partition_point(|element| {
+			element.try_cmp(&value).map(|ord| ord != Ordering::Greater)
+		})?;
+
+		self.elements.insert(index, value);
+		Ok(index)
+	}
+
+	/// Returns an element equal to `value`, if there is one.
+	pub fn try_get(&self, value: &T) -> Result<Option<&T>, T::OrdError> {
+		Ok(self.try_binary_search(value)?.ok().map(|index| &self.elements[index]))
+	}
+
+	/// Returns whether an element equal to `value` is in the vector.
+	pub fn try_contains(&self, value: &T) -> Result<bool, T::OrdError> {
+		Ok(self.try_binary_search(value)?.is_ok())
+	}
+
+	/// Removes and returns an element equal to `value`, if there is one.
+	pub fn try_remove(&mut self, value: &T) -> Result<Option<T>, T::OrdError> {
+		Ok(self.try_binary_search(value)?.ok().map(|index| self.elements.remove(index)))
+	}
+}
+
+impl<T> IntoIterator for TrySortedVec<T> {
+	type Item = T;
+	type IntoIter = alloc::vec::IntoIter<T>;
+
+	#[inline]
+	fn into_iter(self) -> Self::IntoIter {
+		self.elements.into_iter()
+	}
+}
+
+impl<'a, T> IntoIterator for &'a TrySortedVec<T> {
+	type Item = &'a T;
+	type IntoIter = core::slice::Iter<'a, T>;
+
+	#[inline]
+	fn into_iter(self) -> Self::IntoIter {
+		self.elements.iter()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::vec::Vec;
+	use crate::testing::{fallible, retry, values, Budget, Fallible};
+	use super::TrySortedVec;
+
+	fn elements(vec: &TrySortedVec<Fallible>) -> Vec<u32> {
+		values(vec)
+	}
+
+	#[test]
+	fn failures_leave_the_vector_unchanged() {
+		let budget = Budget::new(usize::MAX);
+		let mut vec = TrySortedVec::new();
+
+		for element in fallible(&[5, 1, 3, 3, 9, 7], &budget) {
+			vec.try_insert(element).unwrap();
+		}
+
+		// equal elements are inserted after the existing ones.
+		let element = Fallible::new(3, &budget);
+		assert_eq!(retry(&mut vec, &budget, elements, |vec| vec.try_insert(element)), 3);
+
+		let element = Fallible::new(7, &budget);
+		let removed = retry(&mut vec, &budget, elements, |vec| vec.try_remove(&element));
+		assert_eq!(removed.map(|element| element.value), Some(7));
+
+		assert_eq!(elements(&vec), [1, 3, 3, 3, 5, 9]);
+	}
+}
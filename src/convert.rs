@@ -2,6 +2,10 @@
 //!
 //! Note that [`TryFrom`](core::convert::TryFrom) and [`TryInto`](core::convert::TryInto) are
 //! missing, as they're already a part of the core library.
+//!
+//! Like their std counterparts, [`TryAsRef`] and [`TryAsMut`] are forwarded through references.
+//! With the `alloc` feature, they're also forwarded through `Box`, and (for `TryAsRef`) `Rc` and
+//! `Arc`.
 
 /// The try trait for [`AsRef`].
 pub trait TryAsRef<T: ?Sized> {
@@ -20,3 +24,60 @@ pub trait TryAsMut<T: ?Sized> {
 	/// The fallible equivalent of [`AsMut::as_mut`].
 	fn try_as_mut(&mut self) -> Result<&mut T, Self::Error>;
 }
+
+impl<T: ?Sized + TryAsRef<U>, U: ?Sized> TryAsRef<U> for &T {
+	type Error = T::Error;
+
+	#[inline]
+	fn try_as_ref(&self) -> Result<&U, Self::Error> {
+		(**self).try_as_ref()
+	}
+}
+
+impl<T: ?Sized + TryAsRef<U>, U: ?Sized> TryAsRef<U> for &mut T {
+	type Error = T::Error;
+
+	#[inline]
+	fn try_as_ref(&self) -> Result<&U, Self::Error> {
+		(**self).try_as_ref()
+	}
+}
+
+impl<T: ?Sized + TryAsMut<U>, U: ?Sized> TryAsMut<U> for &mut T {
+	type Error = T::Error;
+
+	#[inline]
+	fn try_as_mut(&mut self) -> Result<&mut U, Self::Error> {
+		(**self).try_as_mut()
+	}
+}
+
+#[cfg(feature = "alloc")]
+mod alloc_impls {
+	use alloc::{boxed::Box, rc::Rc, sync::Arc};
+	use super::{TryAsRef, TryAsMut};
+
+	macro_rules! impl_try_as_ref {
+		($($ptr:ident),*) => {$(
+			impl<T: ?Sized + TryAsRef<U>, U: ?Sized> TryAsRef<U> for $ptr<T> {
+				type Error = T::Error;
+
+				#[inline]
+				fn try_as_ref(&self) -> Result<&U, Self::Error> {
+					(**self).try_as_ref()
+				}
+			}
+		)*};
+	}
+
+	impl_try_as_ref!(Box, Rc, Arc);
+
+	impl<T: ?Sized + TryAsMut<U>, U: ?Sized> TryAsMut<U> for Box<T> {
+		type Error = T::Error;
+
+		#[inline]
+		fn try_as_mut(&mut self) -> Result<&mut U, Self::Error> {
+			(**self).try_as_mut()
+		}
+	}
+}
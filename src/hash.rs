@@ -1,11 +1,7 @@
 //! Try traits for [`core::hash`].
-//!
-//! Note that [`BuildHasher`](core::hash::BuildHasher) isn't implemented because each call to
-//! `build_hasher` should return identical values.
-//!
-//! [`Hasher`] also was not given a `Try` variant as I can see no real use for it (and as it
-//! complicates automatic implementations), , but if there's good reason for one it'll be added.
-use core::hash::{Hash, Hasher};
+use core::fmt;
+use core::hash::{BuildHasher, Hash, Hasher};
+use crate::cmp::{TryPartialEq, TryEq};
 
 /// The try trait for [`Hash`].
 pub trait TryHash {
@@ -26,9 +22,169 @@ pub trait TryHash {
 
 		Ok(())
 	}
+
+	/// Hashes `self` into a [`TryHasher`], reporting failures from both `self` and the hasher.
+	///
+	/// If the hasher fails, nothing more is written to it, and its error is returned even if
+	/// hashing `self` failed afterwards.
+	fn try_hash_fallible<H: TryHasher>(&self, state: &mut H)
+		-> Result<(), HashError<Self::Error, H::Error>>
+	{
+		let mut adapter = TryHasherAdapter { hasher: state, error: None };
+		let result = self.try_hash(&mut adapter);
+
+		if let Some(err) = adapter.error {
+			return Err(HashError::Hasher(err));
+		}
+
+		result.map_err(HashError::Hash)
+	}
+}
+
+/// An object-safe version of [`TryHash`].
+///
+/// Since [`TryHash::try_hash`] is generic, `dyn TryHash` isn't possible; this trait can be used
+/// instead. It's implemented for every `TryHash` type, and `dyn DynTryHash` (optionally with `Send`
+/// and `Sync`) implements `TryHash` in turn.
+pub trait DynTryHash {
+	/// The type returned in the event of an error.
+	type Error;
+
+	/// The same as [`TryHash::try_hash`], except it takes a `dyn Hasher`.
+	fn try_hash_dyn(&self, state: &mut dyn Hasher) -> Result<(), Self::Error>;
+}
+
+impl<T: ?Sized + TryHash> DynTryHash for T {
+	type Error = T::Error;
+
+	#[inline]
+	fn try_hash_dyn(&self, mut state: &mut dyn Hasher) -> Result<(), Self::Error> {
+		self.try_hash(&mut state)
+	}
+}
+
+macro_rules! impl_try_hash_for_dyn {
+	($($bounds:tt)*) => {
+		impl<E> TryHash for dyn DynTryHash<Error = E> $($bounds)* + '_ {
+			type Error = E;
+
+			#[inline]
+			fn try_hash<H: Hasher>(&self, state: &mut H) -> Result<(), Self::Error> {
+				self.try_hash_dyn(state)
+			}
+		}
+	};
+}
+
+impl_try_hash_for_dyn!();
+impl_try_hash_for_dyn!(+ Send);
+impl_try_hash_for_dyn!(+ Send + Sync);
+
+/// The error returned by [`TryHash::try_hash_fallible`] and [`TryBuildHasher::try_hash_one`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HashError<E, H> {
+	/// Hashing the value failed.
+	Hash(E),
+
+	/// Writing to (or building) the hasher failed.
+	Hasher(H),
+}
+
+impl<E: fmt::Display, H: fmt::Display> fmt::Display for HashError<E, H> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Hash(err) => fmt::Display::fmt(err, f),
+			Self::Hasher(err) => fmt::Display::fmt(err, f)
+		}
+	}
 }
 
-impl<T: Hash> TryHash for T {
+macro_rules! try_write_methods {
+	($($method:ident $ty:ty, $std:ident;)*) => {$(
+		#[doc = concat!("The fallible equivalent of [`Hasher::", stringify!($std), "`].")]
+		#[inline]
+		fn $method(&mut self, i: $ty) -> Result<(), Self::Error> {
+			self.try_write(&i.to_ne_bytes())
+		}
+	)*};
+}
+
+/// The try trait for [`Hasher`].
+///
+/// This is useful for hashers whose writes can fail, such as ones that hash into a bounded buffer
+/// or are backed by I/O. Use [`TryHash::try_hash_fallible`] to hash values into it.
+pub trait TryHasher {
+	/// The type returned in the event of an error.
+	type Error;
+
+	/// The fallible equivalent of [`Hasher::finish`].
+	fn try_finish(&self) -> Result<u64, Self::Error>;
+
+	/// The fallible equivalent of [`Hasher::write`].
+	fn try_write(&mut self, bytes: &[u8]) -> Result<(), Self::Error>;
+
+	try_write_methods! {
+		try_write_u8 u8, write_u8;
+		try_write_u16 u16, write_u16;
+		try_write_u32 u32, write_u32;
+		try_write_u64 u64, write_u64;
+		try_write_u128 u128, write_u128;
+		try_write_usize usize, write_usize;
+		try_write_i8 i8, write_i8;
+		try_write_i16 i16, write_i16;
+		try_write_i32 i32, write_i32;
+		try_write_i64 i64, write_i64;
+		try_write_i128 i128, write_i128;
+		try_write_isize isize, write_isize;
+	}
+}
+
+macro_rules! adapter_write_methods {
+	($($method:ident $ty:ty, $std:ident;)*) => {$(
+		fn $std(&mut self, i: $ty) {
+			if self.error.is_none() {
+				self.error = self.hasher.$method(i).err();
+			}
+		}
+	)*};
+}
+
+/// Adapts a [`TryHasher`] into a [`Hasher`], remembering the first error.
+struct TryHasherAdapter<'a, H: TryHasher> {
+	hasher: &'a mut H,
+	error: Option<H::Error>
+}
+
+impl<H: TryHasher> Hasher for TryHasherAdapter<'_, H> {
+	fn finish(&self) -> u64 {
+		// `finish` takes `&self`, so the error can't be recorded; it's extremely unusual to call
+		// `finish` within `hash` anyways.
+		self.hasher.try_finish().unwrap_or(0)
+	}
+
+	fn write(&mut self, bytes: &[u8]) {
+		if self.error.is_none() {
+			self.error = self.hasher.try_write(bytes).err();
+		}
+	}
+
+	adapter_write_methods! {
+		try_write_u8 u8, write_u8;
+		try_write_u16 u16, write_u16;
+		try_write_u32 u32, write_u32;
+		try_write_u64 u64, write_u64;
+		try_write_u128 u128, write_u128;
+		try_write_usize usize, write_usize;
+		try_write_i8 i8, write_i8;
+		try_write_i16 i16, write_i16;
+		try_write_i32 i32, write_i32;
+		try_write_i64 i64, write_i64;
+		try_write_i128 i128, write_i128;
+		try_write_isize isize, write_isize;
+	}
+}
+
+impl<T: ?Sized + Hash> TryHash for T {
 	type Error = crate::Infallible;
 
 	#[inline]
@@ -37,7 +193,147 @@ impl<T: Hash> TryHash for T {
 	}
 
 	#[inline]
-	fn try_hash_slice<H: Hasher>(data: &[Self], state: &mut H) -> Result<(), Self::Error> {
+	fn try_hash_slice<H: Hasher>(data: &[Self], state: &mut H) -> Result<(), Self::Error>
+	where
+		Self: Sized
+	{
 		Ok(Self::hash_slice(data, state))
 	}
 }
+
+/// The try trait for [`BuildHasher`].
+///
+/// Like `BuildHasher`, every hasher built by the same instance should produce the same results for
+/// the same values; however, setting up the hasher can fail, such as when a keyed hasher needs to
+/// obtain its key.
+pub trait TryBuildHasher {
+	/// The type of hasher that's built.
+	type Hasher: Hasher;
+
+	/// The type returned in the event of an error.
+	type Error;
+
+	/// The fallible equivalent of [`BuildHasher::build_hasher`].
+	fn try_build_hasher(&self) -> Result<Self::Hasher, Self::Error>;
+
+	/// The fallible equivalent of [`BuildHasher::hash_one`].
+	fn try_hash_one<T: ?Sized + TryHash>(&self, x: &T)
+		-> Result<u64, HashError<T::Error, Self::Error>>
+	{
+		let mut hasher = self.try_build_hasher().map_err(HashError::Hasher)?;
+		x.try_hash(&mut hasher).map_err(HashError::Hash)?;
+
+		Ok(hasher.finish())
+	}
+}
+
+impl<B: ?Sized + BuildHasher> TryBuildHasher for B {
+	type Hasher = B::Hasher;
+	type Error = crate::Infallible;
+
+	#[inline]
+	fn try_build_hasher(&self) -> Result<Self::Hasher, Self::Error> {
+		Ok(self.build_hasher())
+	}
+}
+
+/// A value whose [`TryHash`] has been computed ahead of time, so it can be used as a key in
+/// collections that require [`Hash`] and [`Eq`] (such as `std`'s `HashMap`).
+///
+/// The hash is computed once, when it's created, with a [`TryBuildHasher`]; every `Hashed` that's
+/// compared against each other should use the same (or an equivalent) builder. The `Hash`
+/// implementation only writes the cached hash.
+///
+/// Equality is determined by first comparing the cached hashes, and then by calling
+/// [`try_eq`](TryPartialEq::try_eq) on the values. If `try_eq` fails, the values are considered
+/// unequal; this means that a key whose comparisons fail won't be found in a map, but the map
+/// itself will still be in a consistent state.
+#[derive(Debug, Clone, Copy)]
+pub struct Hashed<T> {
+	value: T,
+	hash: u64
+}
+
+impl<T: TryHash> Hashed<T> {
+	/// Hashes `value` using `build_hasher`, returning an error if hashing fails.
+	pub fn try_new<B: TryBuildHasher>(value: T, build_hasher: &B)
+		-> Result<Self, HashError<T::Error, B::Error>>
+	{
+		let hash = build_hasher.try_hash_one(&value)?;
+
+		Ok(Self { value, hash })
+	}
+}
+
+impl<T> Hashed<T> {
+	/// Returns the value.
+	#[inline]
+	pub fn get(&self) -> &T {
+		&self.value
+	}
+
+	/// Returns the cached hash of the value.
+	#[inline]
+	pub fn hash_value(&self) -> u64 {
+		self.hash
+	}
+
+	/// Unwraps the value.
+	#[inline]
+	pub fn into_inner(self) -> T {
+		self.value
+	}
+}
+
+impl<T> Hash for Hashed<T> {
+	#[inline]
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		state.write_u64(self.hash);
+	}
+}
+
+impl<T: TryPartialEq> PartialEq for Hashed<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.hash == other.hash && self.value.try_eq(&other.value).unwrap_or(false)
+	}
+}
+
+impl<T: TryEq> Eq for Hashed<T> {}
+
+macro_rules! forward_write_methods {
+	($($method:ident $ty:ty, $std:ident;)*) => {$(
+		#[inline]
+		fn $method(&mut self, i: $ty) -> Result<(), Self::Error> {
+			Ok(self.$std(i))
+		}
+	)*};
+}
+
+impl<H: ?Sized + Hasher> TryHasher for H {
+	type Error = crate::Infallible;
+
+	#[inline]
+	fn try_finish(&self) -> Result<u64, Self::Error> {
+		Ok(self.finish())
+	}
+
+	#[inline]
+	fn try_write(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+		Ok(self.write(bytes))
+	}
+
+	forward_write_methods! {
+		try_write_u8 u8, write_u8;
+		try_write_u16 u16, write_u16;
+		try_write_u32 u32, write_u32;
+		try_write_u64 u64, write_u64;
+		try_write_u128 u128, write_u128;
+		try_write_usize usize, write_usize;
+		try_write_i8 i8, write_i8;
+		try_write_i16 i16, write_i16;
+		try_write_i32 i32, write_i32;
+		try_write_i64 i64, write_i64;
+		try_write_i128 i128, write_i128;
+		try_write_isize isize, write_isize;
+	}
+}
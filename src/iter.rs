@@ -1,3 +1,389 @@
 //! Try traits for [`core::iter`].
+//!
+//! This also contains [`TryIteratorExt`], which has adapters for merging and deduplicating sorted
+//! iterators whose elements are compared with the try traits.
 
-// TODO
+// TODO: try versions of the `iter` traits themselves (`FromIterator`, `Extend`, etc).
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::{FusedIterator, Peekable};
+use crate::cmp::{TryOrd, TryPartialEq, TryPartialOrd};
+
+/// Iterator adapters that compare elements with the try traits.
+///
+/// All of these adapters yield `Result`s; once a comparison fails, its error is yielded, and then
+/// the adapter ends.
+pub trait TryIteratorExt : Iterator + Sized {
+	/// Merges `self` and `other` (which should both be sorted) into a single sorted iterator.
+	///
+	/// When elements are equal, the ones from `self` come first.
+	fn try_merge<J>(self, other: J) -> TryMerge<Self, J::IntoIter>
+	where
+		J: IntoIterator<Item = Self::Item>,
+		Self::Item: TryOrd
+	{
+		self.try_merge_by(other, <Self::Item as TryPartialOrd>::try_le)
+	}
+
+	/// Merges `self` and `other` into a single iterator, using `is_first` to determine whether an
+	/// element from `self` should come before one from `other`.
+	fn try_merge_by<J, E, F>(self, other: J, is_first: F) -> TryMergeBy<Self, J::IntoIter, F>
+	where
+		J: IntoIterator<Item = Self::Item>,
+		F: FnMut(&Self::Item, &Self::Item) -> Result<bool, E>
+	{
+		let right = other.into_iter().peekable();
+
+		TryMergeBy { left: self.peekable(), right, is_first, done: false }
+	}
+
+	/// Merges every iterator in `self` (which should all be sorted) into a single sorted iterator.
+	///
+	/// When elements are equal, the ones from earlier iterators come first. Each element takes
+	/// `O(k)` comparisons, where `k` is the number of iterators.
+	///
+	/// This is only available with the `alloc` feature.
+	#[cfg(feature = "alloc")]
+	fn try_kmerge(self) -> TryKMerge<<Self::Item as IntoIterator>::IntoIter>
+	where
+		Self::Item: IntoIterator,
+		<Self::Item as IntoIterator>::Item: TryOrd
+	{
+		let heads = self
+			.filter_map(|iter| {
+				let mut iter = iter.into_iter();
+				iter.next().map(|head| (head, iter))
+			})
+			.collect();
+
+		TryKMerge { heads, done: false }
+	}
+
+	/// Removes consecutive duplicate elements (which, for sorted iterators, is all duplicates).
+	///
+	/// The first element of each run of duplicates is kept. If a comparison fails, the error is
+	/// yielded in place of the run that was being compared.
+	fn try_dedup_sorted(self) -> TryDedupSorted<Self>
+	where
+		Self::Item: TryPartialEq
+	{
+		TryDedupSorted { iter: self, last: None, done: false }
+	}
+}
+
+impl<I: Iterator> TryIteratorExt for I {}
+
+/// The iterator returned by [`TryIteratorExt::try_merge`].
+pub type TryMerge<I, J> = TryMergeBy<
+	I,
+	J,
+	fn(
+		&<I as Iterator>::Item,
+		&<I as Iterator>::Item
+	) -> Result<bool, <<I as Iterator>::Item as TryPartialOrd>::OrdError>
+>;
+
+/// The iterator returned by [`TryIteratorExt::try_merge_by`].
+pub struct TryMergeBy<I: Iterator, J: Iterator, F> {
+	left: Peekable<I>,
+	right: Peekable<J>,
+	is_first: F,
+	done: bool
+}
+
+impl<I, J, F> fmt::Debug for TryMergeBy<I, J, F>
+where
+	I: Iterator + fmt::Debug,
+	J: Iterator + fmt::Debug,
+	I::Item: fmt::Debug,
+	J::Item: fmt::Debug
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("TryMergeBy")
+			.field("left", &self.left)
+			.field("right", &self.right)
+			.field("done", &self.done)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<I, J, F> Clone for TryMergeBy<I, J, F>
+where
+	I: Iterator + Clone,
+	J: Iterator + Clone,
+	I::Item: Clone,
+	J::Item: Clone,
+	F: Clone
+{
+	fn clone(&self) -> Self {
+		Self {
+			left: self.left.clone(),
+			right: self.right.clone(),
+			is_first: self.is_first.clone(),
+			done: self.done
+		}
+	}
+}
+
+impl<I, J, E, F> Iterator for TryMergeBy<I, J, F>
+where
+	I: Iterator,
+	J: Iterator<Item = I::Item>,
+	F: FnMut(&I::Item, &I::Item) -> Result<bool, E>
+{
+	type Item = Result<I::Item, E>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done {
+			return None;
+		}
+
+		let take_left =
+			match (self.left.peek(), self.right.peek()) {
+				(Some(left), Some(right)) => match (self.is_first)(left, right) {
+					Ok(take_left) => take_left,
+					Err(err) => {
+						self.done = true;
+						return Some(Err(err));
+					}
+				},
+				(Some(_), None) => true,
+				(None, _) => false
+			};
+
+		if take_left {
+			self.left.next().map(Ok)
+		} else {
+			self.right.next().map(Ok)
+		}
+	}
+}
+
+impl<I, J, E, F> FusedIterator for TryMergeBy<I, J, F>
+where
+	I: FusedIterator,
+	J: FusedIterator<Item = I::Item>,
+	F: FnMut(&I::Item, &I::Item) -> Result<bool, E>
+{}
+
+/// The iterator returned by [`TryIteratorExt::try_kmerge`].
+#[cfg(feature = "alloc")]
+pub struct TryKMerge<I: Iterator> {
+	heads: Vec<(I::Item, I)>,
+	done: bool
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator + fmt::Debug> fmt::Debug for TryKMerge<I>
+where
+	I::Item: fmt::Debug
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("TryKMerge").field("heads", &self.heads).field("done", &self.done).finish()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator + Clone> Clone for TryKMerge<I>
+where
+	I::Item: Clone
+{
+	fn clone(&self) -> Self {
+		Self { heads: self.heads.clone(), done: self.done }
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator> Iterator for TryKMerge<I>
+where
+	I::Item: TryOrd
+{
+	type Item = Result<I::Item, <I::Item as TryPartialOrd>::OrdError>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done || self.heads.is_empty() {
+			return None;
+		}
+
+		let mut smallest = 0;
+
+		for idx in 1..self.heads.len() {
+			match self.heads[idx].0.try_lt(&self.heads[smallest].0) {
+				Ok(true) => smallest = idx,
+				Ok(false) => {},
+				Err(err) => {
+					self.done = true;
+					return Some(Err(err));
+				}
+			}
+		}
+
+		let (head, iter) = &mut self.heads[smallest];
+
+		Some(Ok(match iter.next() {
+			Some(next) => core::mem::replace(head, next),
+			None => self.heads.remove(smallest).0
+		}))
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator> FusedIterator for TryKMerge<I> where I::Item: TryOrd {}
+
+/// The iterator returned by [`TryIteratorExt::try_dedup_sorted`].
+pub struct TryDedupSorted<I: Iterator> {
+	iter: I,
+	last: Option<I::Item>,
+	done: bool
+}
+
+impl<I: Iterator + fmt::Debug> fmt::Debug for TryDedupSorted<I>
+where
+	I::Item: fmt::Debug
+{
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("TryDedupSorted")
+			.field("iter", &self.iter)
+			.field("last", &self.last)
+			.field("done", &self.done)
+			.finish()
+	}
+}
+
+impl<I: Iterator + Clone> Clone for TryDedupSorted<I>
+where
+	I::Item: Clone
+{
+	fn clone(&self) -> Self {
+		Self { iter: self.iter.clone(), last: self.last.clone(), done: self.done }
+	}
+}
+
+impl<I: Iterator> Iterator for TryDedupSorted<I>
+where
+	I::Item: TryPartialEq
+{
+	type Item = Result<I::Item, <I::Item as TryPartialEq>::Error>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done {
+			return None;
+		}
+
+		let last = match self.last.take() {
+			Some(last) => last,
+			None => self.iter.next()?
+		};
+
+		for next in &mut self.iter {
+			match last.try_eq(&next) {
+				Ok(true) => {},
+				Ok(false) => {
+					self.last = Some(next);
+					break;
+				},
+				Err(err) => {
+					self.done = true;
+					return Some(Err(err));
+				}
+			}
+		}
+
+		Some(Ok(last))
+	}
+}
+
+impl<I: FusedIterator> FusedIterator for TryDedupSorted<I> where I::Item: TryPartialEq {}
+
+#[cfg(test)]
+mod tests {
+	use std::vec::Vec;
+	use crate::testing::{fallible, Budget, Fallible, OutOfBudget};
+	use super::TryIteratorExt;
+
+	/// Collects the values of `iter`'s items, including the errors and a trailing `None`.
+	fn results<'b>(mut iter: impl Iterator<Item = Result<Fallible<'b>, OutOfBudget>>)
+		-> Vec<Option<Result<u32, OutOfBudget>>>
+	{
+		let mut results = Vec::new();
+
+		loop {
+			let result = iter.next().map(|result| result.map(|item| item.value));
+			results.push(result);
+
+			if result.is_none() {
+				return results;
+			}
+		}
+	}
+
+	#[test]
+	fn merge() {
+		let merged: Result<Vec<_>, _> = [1, 4, 6].iter().try_merge(&[2, 4, 5, 7]).collect();
+		assert_eq!(merged, Ok(std::vec![&1, &2, &4, &4, &5, &6, &7]));
+
+		// equal elements from the left come first.
+		let left = [(1, 'a'), (2, 'a')];
+		let right = [(1, 'b'), (2, 'b')];
+		let merged: Result<Vec<_>, ()> = left.iter()
+			.try_merge_by(&right, |lhs, rhs| Ok(lhs.0 <= rhs.0))
+			.collect();
+		assert_eq!(merged, Ok(std::vec![&(1, 'a'), &(1, 'b'), &(2, 'a'), &(2, 'b')]));
+	}
+
+	#[test]
+	fn merge_ends_after_an_error() {
+		let budget = Budget::new(usize::MAX);
+		let (left, right) = (fallible(&[1, 4, 6], &budget), fallible(&[2, 5], &budget));
+
+		budget.set(2);
+		assert_eq!(
+			results(left.into_iter().try_merge(right)),
+			[Some(Ok(1)), Some(Ok(2)), Some(Err(OutOfBudget)), None]
+		);
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn kmerge() {
+		let budget = Budget::new(usize::MAX);
+		let iters = || std::vec![
+			fallible(&[3, 6], &budget),
+			fallible(&[], &budget),
+			fallible(&[1, 2, 9], &budget),
+			fallible(&[4], &budget)
+		];
+
+		assert_eq!(
+			results(iters().into_iter().try_kmerge()),
+			[Some(Ok(1)), Some(Ok(2)), Some(Ok(3)), Some(Ok(4)), Some(Ok(6)), Some(Ok(9)), None]
+		);
+
+		// picking the first element takes two comparisons, as there are three iterators.
+		budget.set(3);
+		assert_eq!(
+			results(iters().into_iter().try_kmerge()),
+			[Some(Ok(1)), Some(Err(OutOfBudget)), None]
+		);
+	}
+
+	#[test]
+	fn dedup_sorted() {
+		let budget = Budget::new(usize::MAX);
+		let items = || fallible(&[1, 1, 2, 3, 3, 3], &budget);
+
+		assert_eq!(
+			results(items().into_iter().try_dedup_sorted()),
+			[Some(Ok(1)), Some(Ok(2)), Some(Ok(3)), None]
+		);
+
+		// the error takes the place of the run of `2`s.
+		budget.set(2);
+		assert_eq!(
+			results(items().into_iter().try_dedup_sorted()),
+			[Some(Ok(1)), Some(Err(OutOfBudget)), None]
+		);
+	}
+}
@@ -17,16 +17,41 @@
 //! - They simply hadn't been released yet when this was written. If this is the case, file an issue
 //!   (or submit a pull request!)
 #![no_std]
+#![cfg_attr(feature = "nightly_associated_type_defaults", feature(associated_type_defaults))]
 #![forbid(unsafe_code)]
 #![allow(
 	clippy::unit_arg, // to allow for simple `Ok(x = y)` return values.
 	clippy::module_name_repetitions, // the core lib does this all over the place
 )]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(test)]
+extern crate std;
+
 /// The value used for infallible conversions.
 pub type Infallible = core::convert::Infallible;
 
+/// Implements `From<`[`Infallible`]`>` for each of the given error types.
+///
+/// This lets them be used as the error type of [`Lexicographic`](cmp::Lexicographic) and
+/// [`Structural`](structural::Structural) when some elements are infallible (see the
+/// [`structural`] module docs).
+#[macro_export]
+macro_rules! impl_from_infallible {
+	($($ty:ty),* $(,)?) => {$(
+		impl ::core::convert::From<$crate::Infallible> for $ty {
+			#[inline]
+			fn from(never: $crate::Infallible) -> Self {
+				match never {}
+			}
+		}
+	)*};
+}
+
 pub mod clone;
+pub mod collections;
 pub mod borrow;
 pub mod cmp;
 pub mod ops;
@@ -34,4 +59,9 @@ pub mod hash;
 pub mod convert;
 pub mod default;
 pub mod iter;
-// Should we even include `slice`, `any`, `fmt`,	 or `future`?
+pub mod slice;
+pub mod structural;
+// Should we even include `any`, `fmt`, or `future`?
+
+#[cfg(test)]
+mod testing;
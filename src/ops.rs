@@ -7,7 +7,11 @@
 //! `12.try_add(13)?`.
 mod arith;
 mod bit;
+mod checked;
+mod grid;
 mod index;
+mod signed;
+mod str_index;
 
 // `deref` is missing because `try_deref` makes no sense---the whole point is it's infallible
 // `function` is missing because you simply change the return type
@@ -15,4 +19,8 @@ mod index;
 
 pub use arith::*;
 pub use bit::*;
+pub use checked::*;
+pub use grid::*;
 pub use index::*;
+pub use signed::*;
+pub use str_index::*;
@@ -0,0 +1,272 @@
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::{Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo};
+use core::ops::RangeToInclusive;
+use crate::slice::{SliceLike, SliceLikeMut};
+use super::{TryIndex, TryIndexMut};
+
+/// An indexing view that returns errors instead of panicking when indices are out of bounds.
+///
+/// Because of the blanket implementation of [`TryIndex`] for every [`Index`](core::ops::Index),
+/// `[T]::try_index` still panics on invalid indices. Wrapping the storage in `Checked` gives it
+/// implementations that don't.
+///
+/// Any [`SliceLike`] storage (such as `&[T]`, `[T; N]`, or `Vec<T>`) can be indexed by `usize`,
+/// every range type, and `u64`, `u32`, and `i64`. `str`s (either `&str`, `&mut str`, or `String`)
+/// can be indexed by every range type; ranges which don't lie on char boundaries are also
+/// reported as [`RangeError`]s (use [`ByteIndexed`](super::ByteIndexed) to tell them apart).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Checked<S>(pub S);
+
+impl<S> Checked<S> {
+	/// Unwraps the storage.
+	#[inline]
+	pub fn into_inner(self) -> S {
+		self.0
+	}
+}
+
+/// The error returned when indexing a [`Checked`] with an out-of-bounds index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IndexError {
+	/// The index that was used.
+	pub index: usize,
+
+	/// The length of the storage.
+	pub len: usize
+}
+
+impl fmt::Display for IndexError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "index {} is out of bounds for length {}", self.index, self.len)
+	}
+}
+
+/// The error returned when indexing a [`Checked`] with an invalid range.
+///
+/// `start` and `end` are always given as a half-open range; if they can't be represented (such as
+/// for `..=usize::MAX`), `usize::MAX` is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RangeError {
+	/// The start of the range (inclusive).
+	pub start: usize,
+
+	/// The end of the range (exclusive).
+	pub end: usize,
+
+	/// The length of the storage.
+	pub len: usize
+}
+
+impl fmt::Display for RangeError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "range {}..{} is invalid for length {}", self.start, self.end, self.len)
+	}
+}
+
+/// The error returned when indexing a [`Checked`] with an index that isn't a `usize`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntIndexError<I> {
+	/// The index couldn't be converted to a `usize`.
+	Conversion(I),
+
+	/// The converted index was out of bounds.
+	OutOfBounds(IndexError),
+}
+
+impl<I: fmt::Display> fmt::Display for IntIndexError<I> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Conversion(index) => write!(f, "index {} can't be converted to a usize", index),
+			Self::OutOfBounds(err) => fmt::Display::fmt(err, f)
+		}
+	}
+}
+
+/// Converts `range` into a half-open range, checking it against `len`.
+pub(crate) fn checked_range<R: RangeBounds<usize>>(range: &R, len: usize)
+	-> Result<Range<usize>, RangeError>
+{
+	let start = match range.start_bound() {
+		Bound::Included(&start) => Some(start),
+		Bound::Excluded(&start) => start.checked_add(1),
+		Bound::Unbounded => Some(0)
+	};
+
+	let end = match range.end_bound() {
+		Bound::Included(&end) => end.checked_add(1),
+		Bound::Excluded(&end) => Some(end),
+		Bound::Unbounded => Some(len)
+	};
+
+	match (start, end) {
+		(Some(start), Some(end)) if start <= end && end <= len => Ok(start..end),
+		_ => Err(RangeError {
+			start: start.unwrap_or(usize::MAX),
+			end: end.unwrap_or(usize::MAX),
+			len
+		})
+	}
+}
+
+impl<S: SliceLike> TryIndex<usize> for Checked<S> {
+	type Error = IndexError;
+	type Output = S::Item;
+
+	#[inline]
+	fn try_index(&self, index: usize) -> Result<&Self::Output, Self::Error> {
+		let slice = self.0.as_slice();
+
+		slice.get(index).ok_or(IndexError { index, len: slice.len() })
+	}
+}
+
+impl<S: SliceLikeMut> TryIndexMut<usize> for Checked<S> {
+	type MutError = IndexError;
+
+	#[inline]
+	fn try_index_mut(&mut self, index: usize) -> Result<&mut Self::Output, Self::MutError> {
+		let slice = self.0.as_mut_slice();
+		let len = slice.len();
+
+		slice.get_mut(index).ok_or(IndexError { index, len })
+	}
+}
+
+macro_rules! impl_int_index {
+	($($int:ty)*) => {$(
+		impl<S: SliceLike> TryIndex<$int> for Checked<S> {
+			type Error = IntIndexError<$int>;
+			type Output = S::Item;
+
+			#[inline]
+			fn try_index(&self, index: $int) -> Result<&Self::Output, Self::Error> {
+				let index = usize::try_from(index).map_err(|_| IntIndexError::Conversion(index))?;
+
+				self.try_index(index).map_err(IntIndexError::OutOfBounds)
+			}
+		}
+
+		impl<S: SliceLikeMut> TryIndexMut<$int> for Checked<S> {
+			type MutError = IntIndexError<$int>;
+
+			#[inline]
+			fn try_index_mut(&mut self, index: $int)
+				-> Result<&mut Self::Output, Self::MutError>
+			{
+				let index = usize::try_from(index).map_err(|_| IntIndexError::Conversion(index))?;
+
+				self.try_index_mut(index).map_err(IntIndexError::OutOfBounds)
+			}
+		}
+	)*};
+}
+
+impl_int_index!(u64 u32 i64);
+
+macro_rules! impl_range_index {
+	($($range:ty)*) => {$(
+		impl<S: SliceLike> TryIndex<$range> for Checked<S> {
+			type Error = RangeError;
+			type Output = [S::Item];
+
+			#[inline]
+			fn try_index(&self, index: $range) -> Result<&Self::Output, Self::Error> {
+				let slice = self.0.as_slice();
+
+				checked_range(&index, slice.len()).map(|range| &slice[range])
+			}
+		}
+
+		impl<S: SliceLikeMut> TryIndexMut<$range> for Checked<S> {
+			type MutError = RangeError;
+
+			#[inline]
+			fn try_index_mut(&mut self, index: $range)
+				-> Result<&mut Self::Output, Self::MutError>
+			{
+				let slice = self.0.as_mut_slice();
+
+				checked_range(&index, slice.len()).map(move |range| &mut slice[range])
+			}
+		}
+
+		impl TryIndex<$range> for Checked<&str> {
+			type Error = RangeError;
+			type Output = str;
+
+			#[inline]
+			fn try_index(&self, index: $range) -> Result<&Self::Output, Self::Error> {
+				checked_str_range(self.0, &index)
+			}
+		}
+
+		impl TryIndex<$range> for Checked<&mut str> {
+			type Error = RangeError;
+			type Output = str;
+
+			#[inline]
+			fn try_index(&self, index: $range) -> Result<&Self::Output, Self::Error> {
+				checked_str_range(self.0, &index)
+			}
+		}
+
+		impl TryIndexMut<$range> for Checked<&mut str> {
+			type MutError = RangeError;
+
+			#[inline]
+			fn try_index_mut(&mut self, index: $range)
+				-> Result<&mut Self::Output, Self::MutError>
+			{
+				checked_str_range_mut(self.0, &index)
+			}
+		}
+
+		#[cfg(feature = "alloc")]
+		impl TryIndex<$range> for Checked<alloc::string::String> {
+			type Error = RangeError;
+			type Output = str;
+
+			#[inline]
+			fn try_index(&self, index: $range) -> Result<&Self::Output, Self::Error> {
+				checked_str_range(&self.0, &index)
+			}
+		}
+
+		#[cfg(feature = "alloc")]
+		impl TryIndexMut<$range> for Checked<alloc::string::String> {
+			type MutError = RangeError;
+
+			#[inline]
+			fn try_index_mut(&mut self, index: $range)
+				-> Result<&mut Self::Output, Self::MutError>
+			{
+				checked_str_range_mut(&mut self.0, &index)
+			}
+		}
+	)*};
+}
+
+impl_range_index! {
+	Range<usize> RangeFrom<usize> RangeFull RangeInclusive<usize> RangeTo<usize>
+	RangeToInclusive<usize> (Bound<usize>, Bound<usize>)
+}
+
+fn checked_str_range<'a, R: RangeBounds<usize>>(s: &'a str, range: &R)
+	-> Result<&'a str, RangeError>
+{
+	let range = checked_range(range, s.len())?;
+	let (start, end, len) = (range.start, range.end, s.len());
+
+	s.get(range).ok_or(RangeError { start, end, len })
+}
+
+fn checked_str_range_mut<'a, R: RangeBounds<usize>>(s: &'a mut str, range: &R)
+	-> Result<&'a mut str, RangeError>
+{
+	let len = s.len();
+	let range = checked_range(range, len)?;
+	let (start, end) = (range.start, range.end);
+
+	s.get_mut(range).ok_or(RangeError { start, end, len })
+}
@@ -0,0 +1,313 @@
+use core::fmt;
+use crate::slice::{SliceLike, SliceLikeMut};
+use super::{TryIndex, TryIndexMut};
+
+/// A multi-dimensional indexing view over flat, [`SliceLike`] storage.
+///
+/// It implements [`TryIndex<[usize; N]>`](TryIndex) and [`TryIndexMut<[usize; N]>`](TryIndexMut),
+/// as well as `(usize, usize)` and `(usize, usize, usize)` for two- and three-dimensional grids.
+/// Indices are checked against each dimension separately, so an index that's out of bounds along
+/// one axis is an error even if its offset would be within the storage.
+///
+/// The position of each element is given by its index multiplied by the strides. Grids created
+/// with [`try_new`](Self::try_new) are row-major (the last axis is contiguous), but
+/// [`try_with_strides`](Self::try_with_strides) can be used for other layouts, such as images
+/// whose rows are padded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Grid<S, const N: usize> {
+	storage: S,
+	dims: [usize; N],
+	strides: [usize; N]
+}
+
+/// The error returned when a [`Grid`]'s shape doesn't fit its storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShapeError {
+	/// Computing the size of the grid overflowed at `axis`.
+	Overflow {
+		/// The axis at which the overflow happened.
+		axis: usize
+	},
+
+	/// The storage's length doesn't match the grid's shape.
+	LengthMismatch {
+		/// The length the shape requires.
+		expected: usize,
+
+		/// The length of the storage.
+		len: usize
+	},
+}
+
+impl fmt::Display for ShapeError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Overflow { axis } => write!(f, "grid size overflows at axis {}", axis),
+			Self::LengthMismatch { expected, len } =>
+				write!(f, "grid requires a length of {}, but the storage has {}", expected, len)
+		}
+	}
+}
+
+/// The error returned when indexing a [`Grid`] with an out-of-bounds index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GridIndexError {
+	/// The axis along which the index was out of bounds.
+	pub axis: usize,
+
+	/// The index along that axis.
+	pub index: usize,
+
+	/// The size of the grid along that axis.
+	pub len: usize
+}
+
+impl fmt::Display for GridIndexError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let Self { axis, index, len } = self;
+
+		write!(f, "index {} is out of bounds for axis {} of length {}", index, axis, len)
+	}
+}
+
+impl<S: SliceLike, const N: usize> Grid<S, N> {
+	/// Creates a row-major grid with the dimensions `dims`.
+	///
+	/// The storage's length must be exactly the product of `dims`. If any dimension is zero, the
+	/// grid is empty (and so is its storage), even if the other dimensions are huge.
+	pub fn try_new(storage: S, dims: [usize; N]) -> Result<Self, ShapeError> {
+		let mut strides = [0; N];
+		let mut size = 1_usize;
+
+		// an empty grid has no elements, so its strides are never used and can saturate instead
+		// (its size is still zero, as the zero dimension is eventually multiplied in).
+		let is_empty = dims.contains(&0);
+
+		for axis in (0..N).rev() {
+			strides[axis] = size;
+			size = match size.checked_mul(dims[axis]) {
+				Some(size) => size,
+				None if is_empty => usize::MAX,
+				None => return Err(ShapeError::Overflow { axis })
+			};
+		}
+
+		let len = storage.as_slice().len();
+
+		if size != len {
+			return Err(ShapeError::LengthMismatch { expected: size, len });
+		}
+
+		Ok(Self { storage, dims, strides })
+	}
+
+	/// Creates a grid with the dimensions `dims` and the given `strides`.
+	///
+	/// The storage must be long enough to contain the furthest element; any extra elements (such as
+	/// padding between rows) are never accessed.
+	pub fn try_with_strides(storage: S, dims: [usize; N], strides: [usize; N])
+		-> Result<Self, ShapeError>
+	{
+		let len = storage.as_slice().len();
+
+		if dims.contains(&0) {
+			return Ok(Self { storage, dims, strides });
+		}
+
+		let mut last = 0_usize;
+
+		for axis in 0..N {
+			last = (dims[axis] - 1)
+				.checked_mul(strides[axis])
+				.and_then(|offset| last.checked_add(offset))
+				.ok_or(ShapeError::Overflow { axis })?;
+		}
+
+		if last >= len {
+			return Err(ShapeError::LengthMismatch { expected: last + 1, len });
+		}
+
+		Ok(Self { storage, dims, strides })
+	}
+}
+
+impl<S, const N: usize> Grid<S, N> {
+	/// Returns the size of the grid along each axis.
+	#[inline]
+	pub fn dims(&self) -> &[usize; N] {
+		&self.dims
+	}
+
+	/// Returns the distance in the storage between consecutive elements along each axis.
+	#[inline]
+	pub fn strides(&self) -> &[usize; N] {
+		&self.strides
+	}
+
+	/// Returns a reference to the storage.
+	#[inline]
+	pub fn storage(&self) -> &S {
+		&self.storage
+	}
+
+	/// Unwraps the storage.
+	#[inline]
+	pub fn into_inner(self) -> S {
+		self.storage
+	}
+
+	/// Converts `index` into an offset in the storage.
+	fn offset(&self, index: [usize; N]) -> Result<usize, GridIndexError> {
+		for (axis, (&index, &len)) in index.iter().zip(&self.dims).enumerate() {
+			if index >= len {
+				return Err(GridIndexError { axis, index, len });
+			}
+		}
+
+		// Every index is in bounds, so no dimension is zero, and the offset of the furthest element
+		// was checked not to overflow when the grid was created.
+		Ok(index.iter().zip(&self.strides).map(|(&index, &stride)| index * stride).sum())
+	}
+}
+
+impl<S: SliceLike, const N: usize> TryIndex<[usize; N]> for Grid<S, N> {
+	type Error = GridIndexError;
+	type Output = S::Item;
+
+	#[inline]
+	fn try_index(&self, index: [usize; N]) -> Result<&Self::Output, Self::Error> {
+		let offset = self.offset(index)?;
+
+		Ok(&self.storage.as_slice()[offset])
+	}
+}
+
+impl<S: SliceLikeMut, const N: usize> TryIndexMut<[usize; N]> for Grid<S, N> {
+	type MutError = GridIndexError;
+
+	#[inline]
+	fn try_index_mut(&mut self, index: [usize; N]) -> Result<&mut Self::Output, Self::MutError> {
+		let offset = self.offset(index)?;
+
+		Ok(&mut self.storage.as_mut_slice()[offset])
+	}
+}
+
+macro_rules! impl_tuple_index {
+	($($n:literal => ($($idx:ident)*);)*) => {$(
+		impl<S: SliceLike> TryIndex<($(impl_tuple_index!(@usize $idx),)*)> for Grid<S, $n> {
+			type Error = GridIndexError;
+			type Output = S::Item;
+
+			#[inline]
+			fn try_index(&self, ($($idx,)*): ($(impl_tuple_index!(@usize $idx),)*))
+				-> Result<&Self::Output, Self::Error>
+			{
+				self.try_index([$($idx),*])
+			}
+		}
+
+		impl<S: SliceLikeMut> TryIndexMut<($(impl_tuple_index!(@usize $idx),)*)> for Grid<S, $n> {
+			type MutError = GridIndexError;
+
+			#[inline]
+			fn try_index_mut(&mut self, ($($idx,)*): ($(impl_tuple_index!(@usize $idx),)*))
+				-> Result<&mut Self::Output, Self::MutError>
+			{
+				self.try_index_mut([$($idx),*])
+			}
+		}
+	)*};
+	(@usize $idx:ident) => { usize };
+}
+
+impl_tuple_index! {
+	2 => (a b);
+	3 => (a b c);
+}
+
+#[cfg(test)]
+mod tests {
+	use std::vec::Vec;
+	use crate::ops::{TryIndex, TryIndexMut};
+	use super::{Grid, GridIndexError, ShapeError};
+
+	#[test]
+	fn row_major_offsets() {
+		let storage: Vec<_> = (0..24).collect();
+		let grid = Grid::try_new(&storage[..], [2, 3, 4]).unwrap();
+
+		assert_eq!(grid.strides(), &[12, 4, 1]);
+		assert_eq!(grid.try_index([0, 0, 0]), Ok(&0));
+		assert_eq!(grid.try_index([1, 2, 3]), Ok(&23));
+		assert_eq!(grid.try_index((1, 0, 2)), Ok(&14));
+
+		let mut storage: Vec<_> = (0..6).collect();
+		let mut grid = Grid::try_new(&mut storage[..], [2, 3]).unwrap();
+		*grid.try_index_mut((1, 1)).unwrap() = 40;
+		*grid.try_index_mut([0, 2]).unwrap() = 20;
+		assert_eq!(storage, [0, 1, 20, 3, 40, 5]);
+	}
+
+	#[test]
+	fn out_of_bounds_axis() {
+		let storage = [0; 6];
+		let grid = Grid::try_new(&storage[..], [2, 3]).unwrap();
+
+		// the offset of `[0, 3]` would be within the storage, but it's still out of bounds.
+		assert_eq!(grid.try_index([0, 3]), Err(GridIndexError { axis: 1, index: 3, len: 3 }));
+		assert_eq!(grid.try_index((2, 0)), Err(GridIndexError { axis: 0, index: 2, len: 2 }));
+	}
+
+	#[test]
+	fn shape_errors() {
+		let storage = [0; 6];
+
+		assert_eq!(
+			Grid::try_new(&storage[..], [2, 4]),
+			Err(ShapeError::LengthMismatch { expected: 8, len: 6 })
+		);
+		assert_eq!(
+			Grid::try_new(&storage[..], [2, usize::MAX]),
+			Err(ShapeError::Overflow { axis: 0 })
+		);
+		assert_eq!(
+			Grid::try_with_strides(&storage[..], [2, 3], [4, 1]),
+			Err(ShapeError::LengthMismatch { expected: 7, len: 6 })
+		);
+		assert_eq!(
+			Grid::try_with_strides(&storage[..], [2, 3], [usize::MAX, 1]),
+			Err(ShapeError::Overflow { axis: 1 })
+		);
+	}
+
+	#[test]
+	fn padded_strides() {
+		// two rows of three elements, each padded to four.
+		let storage = [0, 1, 2, 99, 3, 4, 5];
+		let grid = Grid::try_with_strides(&storage[..], [2, 3], [4, 1]).unwrap();
+
+		assert_eq!(grid.try_index((1, 0)), Ok(&3));
+		assert_eq!(grid.try_index((1, 2)), Ok(&5));
+		assert_eq!(grid.try_index((0, 3)), Err(GridIndexError { axis: 1, index: 3, len: 3 }));
+	}
+
+	#[test]
+	fn zero_dimensions() {
+		let storage: [u8; 0] = [];
+
+		let grid = Grid::try_new(&storage[..], [0, usize::MAX, usize::MAX]).unwrap();
+		assert_eq!(
+			grid.try_index([0, 0, 0]),
+			Err(GridIndexError { axis: 0, index: 0, len: 0 })
+		);
+
+		let grid = Grid::try_with_strides(&storage[..], [usize::MAX, 0], [usize::MAX, 1]).unwrap();
+		assert_eq!(grid.try_index([3, 0]), Err(GridIndexError { axis: 1, index: 0, len: 0 }));
+
+		assert_eq!(
+			Grid::try_new(&[1][..], [3, 0]),
+			Err(ShapeError::LengthMismatch { expected: 0, len: 1 })
+		);
+	}
+}
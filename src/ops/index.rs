@@ -1,6 +1,6 @@
 use core::ops::{Index, IndexMut};
 
-///
+/// The try trait for [`Index`].
 pub trait TryIndex<Idx> {
 	/// The type returned in the event of an error.
 	type Error;
@@ -13,12 +13,22 @@ pub trait TryIndex<Idx> {
 }
 
 /// The try trait for [`IndexMut`].
+///
+/// See [`TryBorrowMut`](crate::borrow::TryBorrowMut) for why `MutError` is separate from `Error`.
 pub trait TryIndexMut<Idx> : TryIndex<Idx> {
-	/// The fallible equivalent of [`Index::index`].
-	fn try_index_mut(&mut self, index: Idx) -> Result<&mut Self::Output, Self::Error>;
+	/// The type returned in the event of an error when indexing mutably.
+	#[cfg(feature = "nightly_associated_type_defaults")]
+	type MutError = <Self as TryIndex<Idx>>::Error;
+
+	/// The type returned in the event of an error when indexing mutably.
+	#[cfg(not(feature = "nightly_associated_type_defaults"))]
+	type MutError;
+
+	/// The fallible equivalent of [`IndexMut::index_mut`].
+	fn try_index_mut(&mut self, index: Idx) -> Result<&mut Self::Output, Self::MutError>;
 }
 
-impl<T: Index<Idx>, Idx> TryIndex<Idx> for T {
+impl<T: ?Sized + Index<Idx>, Idx> TryIndex<Idx> for T {
 	type Error = crate::Infallible;
 	type Output = <Self as Index<Idx>>::Output;
 
@@ -27,8 +37,10 @@ impl<T: Index<Idx>, Idx> TryIndex<Idx> for T {
 	}
 }
 
-impl<T: IndexMut<Idx>, Idx> TryIndexMut<Idx> for T {
-	fn try_index_mut(&mut self, index: Idx) -> Result<&mut Self::Output, Self::Error> {
+impl<T: ?Sized + IndexMut<Idx>, Idx> TryIndexMut<Idx> for T {
+	type MutError = crate::Infallible;
+
+	fn try_index_mut(&mut self, index: Idx) -> Result<&mut Self::Output, Self::MutError> {
 		Ok(self.index_mut(index))
 	}
 }
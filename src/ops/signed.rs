@@ -0,0 +1,339 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::mem;
+use crate::slice::{SliceLike, SliceLikeMut};
+use super::{TryIndex, TryIndexMut};
+
+/// An indexing view that accepts signed indices, where negative indices count back from the end
+/// (like in Python). `-1` is the last element, `-2` is the one before it, and so on.
+///
+/// It implements [`TryIndex<isize>`] and [`TryIndexMut<isize>`] for any [`SliceLike`] storage, and
+/// [`try_step`](Self::try_step) can be used to take Python-style `start:stop:step` slices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Signed<S>(pub S);
+
+impl<S> Signed<S> {
+	/// Unwraps the storage.
+	#[inline]
+	pub fn into_inner(self) -> S {
+		self.0
+	}
+}
+
+/// The error returned when a [`Signed`] index is out of bounds, even after counting back from the
+/// end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SignedIndexError {
+	/// The index that was used.
+	pub index: isize,
+
+	/// The length of the storage.
+	pub len: usize
+}
+
+impl fmt::Display for SignedIndexError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "index {} is out of bounds for length {}", self.index, self.len)
+	}
+}
+
+/// Resolves `index` against `len`, returning a position in `0..=len`.
+fn resolve(index: isize, len: usize) -> Result<usize, SignedIndexError> {
+	let position =
+		if index < 0 {
+			len.checked_sub(index.unsigned_abs())
+		} else {
+			Some(index as usize).filter(|&index| index <= len)
+		};
+
+	position.ok_or(SignedIndexError { index, len })
+}
+
+/// Resolves `index` against `len`, returning a position in `0..len`.
+fn resolve_element(index: isize, len: usize) -> Result<usize, SignedIndexError> {
+	resolve(index, len)
+		.ok()
+		.filter(|&position| position < len)
+		.ok_or(SignedIndexError { index, len })
+}
+
+impl<S: SliceLike> TryIndex<isize> for Signed<S> {
+	type Error = SignedIndexError;
+	type Output = S::Item;
+
+	#[inline]
+	fn try_index(&self, index: isize) -> Result<&Self::Output, Self::Error> {
+		let slice = self.0.as_slice();
+
+		resolve_element(index, slice.len()).map(|index| &slice[index])
+	}
+}
+
+impl<S: SliceLikeMut> TryIndexMut<isize> for Signed<S> {
+	type MutError = SignedIndexError;
+
+	#[inline]
+	fn try_index_mut(&mut self, index: isize) -> Result<&mut Self::Output, Self::MutError> {
+		let slice = self.0.as_mut_slice();
+
+		resolve_element(index, slice.len()).map(move |index| &mut slice[index])
+	}
+}
+
+/// A Python-style `start:stop:step` slice, for use with [`Signed::try_step`].
+///
+/// `start` and `stop` may be negative, in which case they count back from the end. When they're
+/// `None`, they default to the first and one-past-the-last elements in the direction of `step`.
+///
+/// Unlike Python, out-of-range bounds are errors rather than being clamped. With a positive
+/// `step`, `start` and `stop` must lie within `0..=len`; with a negative `step`, `start` is the
+/// first element yielded, so it must lie within `0..len`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StepRange {
+	/// The first index (inclusive).
+	pub start: Option<isize>,
+
+	/// The last index (exclusive).
+	pub stop: Option<isize>,
+
+	/// The distance between indices; this may be negative, but not zero.
+	pub step: isize
+}
+
+impl StepRange {
+	/// Creates a new `StepRange`; this is the same as `start:stop:step` in Python.
+	#[inline]
+	pub const fn new(start: Option<isize>, stop: Option<isize>, step: isize) -> Self {
+		Self { start, stop, step }
+	}
+
+	/// Resolves `self` against `len`, returning the range that's covered (as a half-open range)
+	/// and whether it's traversed in reverse.
+	fn resolve(&self, len: usize) -> Result<(usize, usize, bool), StepError> {
+		if self.step == 0 {
+			return Err(StepError::ZeroStep);
+		}
+
+		if self.step > 0 {
+			let start = self.start.map_or(Ok(0), |start| resolve(start, len))?;
+			let stop = self.stop.map_or(Ok(len), |stop| resolve(stop, len))?;
+
+			return Ok((start, stop.max(start), false));
+		}
+
+		// Everything is shifted up by one, so that "one before the first element" is `0`.
+		let start = match self.start {
+			Some(start) => resolve_element(start, len)? + 1,
+			None => len
+		};
+		let stop = self.stop.map_or(Ok(0), |stop| resolve(stop, len).map(|stop| stop + 1))?;
+
+		Ok((stop.min(start), start, true))
+	}
+}
+
+/// The error returned by [`Signed::try_step`] and [`Signed::try_step_mut`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StepError {
+	/// The step was zero.
+	ZeroStep,
+
+	/// `start` or `stop` was out of bounds.
+	OutOfRange(SignedIndexError),
+}
+
+impl From<SignedIndexError> for StepError {
+	#[inline]
+	fn from(err: SignedIndexError) -> Self {
+		Self::OutOfRange(err)
+	}
+}
+
+impl fmt::Display for StepError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::ZeroStep => write!(f, "step cannot be zero"),
+			Self::OutOfRange(err) => fmt::Display::fmt(err, f)
+		}
+	}
+}
+
+impl<S: SliceLike> Signed<S> {
+	/// Returns an iterator over the elements selected by `range`.
+	pub fn try_step(&self, range: StepRange) -> Result<Stepped<'_, S::Item>, StepError> {
+		let slice = self.0.as_slice();
+		let (start, end, reversed) = range.resolve(slice.len())?;
+
+		Ok(Stepped { slice: &slice[start..end], step: range.step.unsigned_abs(), reversed })
+	}
+}
+
+impl<S: SliceLikeMut> Signed<S> {
+	/// Returns an iterator over mutable references to the elements selected by `range`.
+	pub fn try_step_mut(&mut self, range: StepRange)
+		-> Result<SteppedMut<'_, S::Item>, StepError>
+	{
+		let slice = self.0.as_mut_slice();
+		let (start, end, reversed) = range.resolve(slice.len())?;
+
+		Ok(SteppedMut { slice: &mut slice[start..end], step: range.step.unsigned_abs(), reversed })
+	}
+}
+
+/// The iterator returned by [`Signed::try_step`].
+#[derive(Debug, Clone)]
+pub struct Stepped<'a, T> {
+	slice: &'a [T],
+	step: usize,
+	reversed: bool
+}
+
+impl<'a, T> Iterator for Stepped<'a, T> {
+	type Item = &'a T;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let (item, rest) =
+			if self.reversed {
+				let (item, rest) = self.slice.split_last()?;
+				(item, &rest[..rest.len().saturating_sub(self.step - 1)])
+			} else {
+				let (item, rest) = self.slice.split_first()?;
+				(item, rest.get(self.step - 1..).unwrap_or_default())
+			};
+
+		self.slice = rest;
+		Some(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.slice.len() / self.step + usize::from(self.slice.len() % self.step != 0);
+		(len, Some(len))
+	}
+}
+
+impl<T> ExactSizeIterator for Stepped<'_, T> {}
+impl<T> FusedIterator for Stepped<'_, T> {}
+
+/// The iterator returned by [`Signed::try_step_mut`].
+#[derive(Debug)]
+pub struct SteppedMut<'a, T> {
+	slice: &'a mut [T],
+	step: usize,
+	reversed: bool
+}
+
+impl<'a, T> Iterator for SteppedMut<'a, T> {
+	type Item = &'a mut T;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let slice = mem::take(&mut self.slice);
+		let (item, rest) =
+			if self.reversed {
+				let (item, rest) = slice.split_last_mut()?;
+				let keep = rest.len().saturating_sub(self.step - 1);
+				(item, &mut rest[..keep])
+			} else {
+				let (item, rest) = slice.split_first_mut()?;
+				(item, rest.get_mut(self.step - 1..).unwrap_or_default())
+			};
+
+		self.slice = rest;
+		Some(item)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.slice.len() / self.step + usize::from(self.slice.len() % self.step != 0);
+		(len, Some(len))
+	}
+}
+
+impl<T> ExactSizeIterator for SteppedMut<'_, T> {}
+impl<T> FusedIterator for SteppedMut<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+	use std::vec::Vec;
+	use crate::ops::{TryIndex, TryIndexMut};
+	use super::{Signed, SignedIndexError, StepError, StepRange};
+
+	const DATA: [u32; 8] = [0, 1, 2, 3, 4, 5, 6, 7];
+
+	/// Returns the elements of `DATA` selected by `start:stop:step`.
+	fn step(start: Option<isize>, stop: Option<isize>, step: isize)
+		-> Result<Vec<u32>, StepError>
+	{
+		let range = StepRange::new(start, stop, step);
+		let stepped = Signed(&DATA).try_step(range)?;
+		let len = stepped.len();
+		let elements: Vec<_> = stepped.copied().collect();
+
+		assert_eq!(len, elements.len());
+		Ok(elements)
+	}
+
+	#[test]
+	fn index() {
+		let mut signed = Signed(DATA);
+
+		assert_eq!(signed.try_index(0), Ok(&0));
+		assert_eq!(signed.try_index(-1), Ok(&7));
+		assert_eq!(signed.try_index(-8), Ok(&0));
+		assert_eq!(signed.try_index(8), Err(SignedIndexError { index: 8, len: 8 }));
+		assert_eq!(signed.try_index(-9), Err(SignedIndexError { index: -9, len: 8 }));
+		assert_eq!(
+			signed.try_index(isize::MIN),
+			Err(SignedIndexError { index: isize::MIN, len: 8 })
+		);
+
+		*signed.try_index_mut(-2).unwrap() = 60;
+		assert_eq!(signed.0[6], 60);
+	}
+
+	#[test]
+	fn positive_steps() {
+		assert_eq!(step(None, None, 1), Ok(DATA.to_vec()));
+		assert_eq!(step(Some(1), Some(-1), 2), Ok(std::vec![1, 3, 5]));
+		assert_eq!(step(Some(2), None, 3), Ok(std::vec![2, 5]));
+		assert_eq!(step(Some(5), Some(2), 1), Ok(std::vec![]));
+		assert_eq!(step(None, None, isize::MAX), Ok(std::vec![0]));
+	}
+
+	#[test]
+	fn negative_steps() {
+		assert_eq!(step(None, None, -1), Ok(std::vec![7, 6, 5, 4, 3, 2, 1, 0]));
+		assert_eq!(step(Some(5), Some(2), -1), Ok(std::vec![5, 4, 3]));
+		assert_eq!(step(Some(-1), None, -3), Ok(std::vec![7, 4, 1]));
+		assert_eq!(step(Some(2), Some(5), -1), Ok(std::vec![]));
+		assert_eq!(step(None, None, isize::MIN), Ok(std::vec![7]));
+	}
+
+	#[test]
+	fn step_errors() {
+		assert_eq!(step(None, None, 0), Err(StepError::ZeroStep));
+		assert_eq!(
+			step(Some(9), None, 1),
+			Err(StepError::OutOfRange(SignedIndexError { index: 9, len: 8 }))
+		);
+
+		// with a negative step, `start` is the first element yielded, so it can't be `len`.
+		assert_eq!(
+			step(Some(8), None, -1),
+			Err(StepError::OutOfRange(SignedIndexError { index: 8, len: 8 }))
+		);
+	}
+
+	#[test]
+	fn step_mut() {
+		let mut signed = Signed(DATA);
+		let stepped = signed.try_step_mut(StepRange::new(Some(-2), None, -2)).unwrap();
+		assert_eq!(stepped.len(), 4);
+
+		for element in stepped {
+			*element *= 10;
+		}
+
+		assert_eq!(signed.0, [0, 1, 20, 3, 40, 5, 60, 7]);
+	}
+}
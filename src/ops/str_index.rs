@@ -0,0 +1,232 @@
+use core::fmt;
+use core::iter;
+use core::ops::{Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo};
+use core::ops::RangeToInclusive;
+use super::checked::checked_range;
+use super::{RangeError, TryIndex, TryIndexMut};
+
+/// A string indexing view that takes byte ranges, and returns errors instead of panicking when
+/// they're out of bounds or don't lie on char boundaries.
+///
+/// It can wrap anything that implements [`AsRef<str>`] (such as `&str` or `String`), and can be
+/// indexed by every range type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ByteIndexed<S>(pub S);
+
+/// A string indexing view that takes char positions instead of byte positions.
+///
+/// It can wrap anything that implements [`AsRef<str>`] (such as `&str` or `String`). Indexing by a
+/// `usize` returns the char at that position (as a `str`), and indexing by a range returns the
+/// chars in it. Since finding a char position requires walking the string, indexing takes `O(n)`
+/// time.
+///
+/// Positions are always `char`s, not graphemes; a single user-perceived character may span
+/// multiple positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CharIndexed<S>(pub S);
+
+impl<S> ByteIndexed<S> {
+	/// Unwraps the storage.
+	#[inline]
+	pub fn into_inner(self) -> S {
+		self.0
+	}
+}
+
+impl<S> CharIndexed<S> {
+	/// Unwraps the storage.
+	#[inline]
+	pub fn into_inner(self) -> S {
+		self.0
+	}
+}
+
+/// The error returned when indexing a [`ByteIndexed`] or [`CharIndexed`] fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StrIndexError {
+	/// `byte` is in bounds, but isn't on a char boundary.
+	NotCharBoundary {
+		/// The offending byte offset.
+		byte: usize
+	},
+
+	/// The range was out of bounds. Its positions are in bytes for [`ByteIndexed`], and in chars
+	/// for [`CharIndexed`].
+	OutOfBounds(RangeError),
+}
+
+impl fmt::Display for StrIndexError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::NotCharBoundary { byte } => write!(f, "byte {} is not a char boundary", byte),
+			Self::OutOfBounds(err) => fmt::Display::fmt(err, f)
+		}
+	}
+}
+
+impl From<RangeError> for StrIndexError {
+	#[inline]
+	fn from(err: RangeError) -> Self {
+		Self::OutOfBounds(err)
+	}
+}
+
+/// Checks that `range` is a valid byte range of `s`.
+fn byte_range<R: RangeBounds<usize>>(s: &str, range: &R) -> Result<Range<usize>, StrIndexError> {
+	let range = checked_range(range, s.len())?;
+
+	for &byte in &[range.start, range.end] {
+		if !s.is_char_boundary(byte) {
+			return Err(StrIndexError::NotCharBoundary { byte });
+		}
+	}
+
+	Ok(range)
+}
+
+/// Converts the char range `range` into a byte range of `s`.
+fn char_range<R: RangeBounds<usize>>(s: &str, range: &R) -> Result<Range<usize>, StrIndexError> {
+	let range = checked_range(range, s.chars().count())?;
+	let mut boundaries = s.char_indices().map(|(byte, _)| byte).chain(iter::once(s.len()));
+
+	// `checked_range` ensures that both of these exist.
+	let start = boundaries.nth(range.start).unwrap_or(s.len());
+	let end =
+		if range.is_empty() {
+			start
+		} else {
+			boundaries.nth(range.end - range.start - 1).unwrap_or(s.len())
+		};
+
+	Ok(start..end)
+}
+
+impl<S: AsRef<str>> TryIndex<usize> for CharIndexed<S> {
+	type Error = StrIndexError;
+	type Output = str;
+
+	#[inline]
+	fn try_index(&self, index: usize) -> Result<&Self::Output, Self::Error> {
+		self.try_index(index..=index)
+	}
+}
+
+impl<S: AsRef<str> + AsMut<str>> TryIndexMut<usize> for CharIndexed<S> {
+	type MutError = StrIndexError;
+
+	#[inline]
+	fn try_index_mut(&mut self, index: usize) -> Result<&mut Self::Output, Self::MutError> {
+		self.try_index_mut(index..=index)
+	}
+}
+
+macro_rules! impl_str_index {
+	($($view:ident $to_bytes:ident;)*) => {$(
+		impl_str_index!(@ranges $view $to_bytes;
+			Range<usize> RangeFrom<usize> RangeFull RangeInclusive<usize> RangeTo<usize>
+			RangeToInclusive<usize> (Bound<usize>, Bound<usize>)
+		);
+	)*};
+	(@ranges $view:ident $to_bytes:ident; $($range:ty)*) => {$(
+		impl<S: AsRef<str>> TryIndex<$range> for $view<S> {
+			type Error = StrIndexError;
+			type Output = str;
+
+			#[inline]
+			fn try_index(&self, index: $range) -> Result<&Self::Output, Self::Error> {
+				let s = self.0.as_ref();
+
+				$to_bytes(s, &index).map(|range| &s[range])
+			}
+		}
+
+		impl<S: AsRef<str> + AsMut<str>> TryIndexMut<$range> for $view<S> {
+			type MutError = StrIndexError;
+
+			#[inline]
+			fn try_index_mut(&mut self, index: $range)
+				-> Result<&mut Self::Output, Self::MutError>
+			{
+				let s = self.0.as_mut();
+				let range = $to_bytes(s, &index)?;
+
+				Ok(&mut s[range])
+			}
+		}
+	)*};
+}
+
+impl_str_index! {
+	ByteIndexed byte_range;
+	CharIndexed char_range;
+}
+
+#[cfg(test)]
+mod tests {
+	use core::ops::Bound;
+	use std::string::String;
+	use crate::ops::{RangeError, TryIndex, TryIndexMut};
+	use super::{ByteIndexed, CharIndexed, StrIndexError};
+
+	#[test]
+	fn char_ranges() {
+		let s = CharIndexed("héllo");
+
+		assert_eq!(s.try_index(1..3), Ok("él"));
+		assert_eq!(s.try_index(..=1), Ok("hé"));
+		assert_eq!(s.try_index(3..), Ok("lo"));
+		assert_eq!(s.try_index(..), Ok("héllo"));
+		assert_eq!(s.try_index(5..5), Ok(""));
+		assert_eq!(s.try_index((Bound::Excluded(0), Bound::Included(1))), Ok("é"));
+		assert_eq!(
+			s.try_index(2..6),
+			Err(StrIndexError::OutOfBounds(RangeError { start: 2, end: 6, len: 5 }))
+		);
+	}
+
+	#[test]
+	fn char_index() {
+		let s = CharIndexed("héllo");
+
+		assert_eq!(s.try_index(1), Ok("é"));
+		assert_eq!(s.try_index(4), Ok("o"));
+		assert_eq!(
+			s.try_index(5),
+			Err(StrIndexError::OutOfBounds(RangeError { start: 5, end: 6, len: 5 }))
+		);
+	}
+
+	#[test]
+	fn byte_ranges() {
+		let s = ByteIndexed("héllo");
+
+		assert_eq!(s.try_index(1..3), Ok("é"));
+		assert_eq!(s.try_index(..2), Err(StrIndexError::NotCharBoundary { byte: 2 }));
+		assert_eq!(s.try_index(2..), Err(StrIndexError::NotCharBoundary { byte: 2 }));
+		assert_eq!(
+			s.try_index(3..7),
+			Err(StrIndexError::OutOfBounds(RangeError { start: 3, end: 7, len: 6 }))
+		);
+	}
+
+	#[test]
+	fn index_mut() {
+		let mut s = CharIndexed(String::from("héllo"));
+
+		s.try_index_mut(2..4).unwrap().make_ascii_uppercase();
+		s.try_index_mut(0).unwrap().make_ascii_uppercase();
+		assert_eq!(s.0, "HéLLo");
+		assert_eq!(
+			s.try_index_mut(5).map(|s| &*s),
+			Err(StrIndexError::OutOfBounds(RangeError { start: 5, end: 6, len: 5 }))
+		);
+
+		let mut s = ByteIndexed(String::from("héllo"));
+		s.try_index_mut(3..).unwrap().make_ascii_uppercase();
+		assert_eq!(s.0, "héLLO");
+		assert_eq!(
+			s.try_index_mut(..2).map(|s| &*s),
+			Err(StrIndexError::NotCharBoundary { byte: 2 })
+		);
+	}
+}
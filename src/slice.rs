@@ -0,0 +1,813 @@
+//! Fallible versions of [`slice`] methods.
+//!
+//! The sorting methods only ever move elements by swapping or rotating them, so if a comparison
+//! fails partway through, the slice is left as some permutation of its original elements.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::iter::FusedIterator;
+use crate::cmp::{TryOrd, TryPartialEq, TryPartialOrd};
+
+/// Slices smaller than this are sorted with insertion sort.
+const INSERTION_SORT_THRESHOLD: usize = 16;
+
+/// Stable insertion sort.
+fn insertion_sort<T, E, F>(v: &mut [T], is_less: &mut F) -> Result<(), E>
+where
+	F: FnMut(&T, &T) -> Result<bool, E>
+{
+	for i in 1..v.len() {
+		let mut j = i;
+
+		while j > 0 && is_less(&v[j], &v[j - 1])? {
+			v.swap(j, j - 1);
+			j -= 1;
+		}
+	}
+
+	Ok(())
+}
+
+/// Returns the index of the first element of `v` that `pred` returns false for, assuming `v` is
+/// partitioned by it.
+fn partition_point<T, E, F>(v: &[T], mut pred: F) -> Result<usize, E>
+where
+	F: FnMut(&T) -> Result<bool, E>
+{
+	let (mut low, mut high) = (0, v.len());
+
+	while low < high {
+		let mid = low + (high - low) / 2;
+
+		if pred(&v[mid])? {
+			low = mid + 1;
+		} else {
+			high = mid;
+		}
+	}
+
+	Ok(low)
+}
+
+/// Stably merges the sorted runs `v[..mid]` and `v[mid..]` in place, using rotations.
+fn merge<T, E, F>(v: &mut [T], mid: usize, is_less: &mut F) -> Result<(), E>
+where
+	F: FnMut(&T, &T) -> Result<bool, E>
+{
+	let (left_len, right_len) = (mid, v.len() - mid);
+
+	if left_len == 0 || right_len == 0 {
+		return Ok(());
+	}
+
+	if left_len + right_len == 2 {
+		if is_less(&v[1], &v[0])? {
+			v.swap(0, 1);
+		}
+
+		return Ok(());
+	}
+
+	// split the larger run in half, and find where its midpoint goes in the other run.
+	let (left_cut, right_cut) =
+		if left_len > right_len {
+			let left_cut = left_len / 2;
+			let (left, right) = v.split_at(mid);
+			let pivot = &left[left_cut];
+			(left_cut, partition_point(right, |x| is_less(x, pivot))?)
+		} else {
+			let right_cut = right_len / 2;
+			let (left, right) = v.split_at(mid);
+			let pivot = &right[right_cut];
+			(partition_point(left, |x| is_less(pivot, x).map(|less| !less))?, right_cut)
+		};
+
+	v[left_cut..mid + right_cut].rotate_left(mid - left_cut);
+
+	let new_mid = left_cut + right_cut;
+	let (left, right) = v.split_at_mut(new_mid);
+	merge(left, left_cut, is_less)?;
+	merge(right, mid - left_cut, is_less)
+}
+
+/// Stable in-place merge sort.
+fn merge_sort<T, E, F>(v: &mut [T], is_less: &mut F) -> Result<(), E>
+where
+	F: FnMut(&T, &T) -> Result<bool, E>
+{
+	if v.len() <= INSERTION_SORT_THRESHOLD {
+		return insertion_sort(v, is_less);
+	}
+
+	let mid = v.len() / 2;
+	merge_sort(&mut v[..mid], is_less)?;
+	merge_sort(&mut v[mid..], is_less)?;
+	merge(v, mid, is_less)
+}
+
+/// Sifts `v[node]` down the max-heap `v[..end]`.
+fn sift_down<T, E, F>(v: &mut [T], mut node: usize, end: usize, is_less: &mut F) -> Result<(), E>
+where
+	F: FnMut(&T, &T) -> Result<bool, E>
+{
+	loop {
+		let mut child = 2 * node + 1;
+
+		if child >= end {
+			return Ok(());
+		}
+
+		if child + 1 < end && is_less(&v[child], &v[child + 1])? {
+			child += 1;
+		}
+
+		if !is_less(&v[node], &v[child])? {
+			return Ok(());
+		}
+
+		v.swap(node, child);
+		node = child;
+	}
+}
+
+/// Unstable in-place heapsort.
+fn heapsort<T, E, F>(v: &mut [T], is_less: &mut F) -> Result<(), E>
+where
+	F: FnMut(&T, &T) -> Result<bool, E>
+{
+	for node in (0..v.len() / 2).rev() {
+		sift_down(v, node, v.len(), is_less)?;
+	}
+
+	for end in (1..v.len()).rev() {
+		v.swap(0, end);
+		sift_down(v, 0, end, is_less)?;
+	}
+
+	Ok(())
+}
+
+/// Moves the median of `v[a]`, `v[b]` and `v[c]` to `v[a]`.
+fn median_of_three<T, E, F>(v: &mut [T], a: usize, b: usize, c: usize, is_less: &mut F)
+	-> Result<(), E>
+where
+	F: FnMut(&T, &T) -> Result<bool, E>
+{
+	// Order `b` and `c`, and then put the median of all three in `a`.
+	let (b, c) = if is_less(&v[c], &v[b])? { (c, b) } else { (b, c) };
+
+	if is_less(&v[a], &v[b])? {
+		v.swap(a, b);
+	} else if is_less(&v[c], &v[a])? {
+		v.swap(a, c);
+	}
+
+	Ok(())
+}
+
+/// Reorders `v` so that the element at `index` is in its final sorted position.
+///
+/// This is quickselect with a three-way partition, so runs of equal elements don't cause
+/// quadratic behaviour. If partitioning isn't making progress, it falls back to heapsort, so the
+/// worst case is `O(n log n)`.
+fn select_nth<T, E, F>(v: &mut [T], index: usize, is_less: &mut F) -> Result<(), E>
+where
+	F: FnMut(&T, &T) -> Result<bool, E>
+{
+	let (mut low, mut high) = (0, v.len());
+	let mut budget = 2 * (usize::BITS - v.len().leading_zeros());
+
+	loop {
+		if high - low <= INSERTION_SORT_THRESHOLD {
+			return insertion_sort(&mut v[low..high], is_less);
+		}
+
+		if budget == 0 {
+			return heapsort(&mut v[low..high], is_less);
+		}
+
+		budget -= 1;
+		median_of_three(v, low, low + (high - low) / 2, high - 1, is_less)?;
+
+		// `v[low..equal]` is less than the pivot, `v[equal..idx]` is equal to it, and
+		// `v[greater..high]` is greater than it. `v[equal]` is always the pivot (or an element
+		// equal to it).
+		let (mut equal, mut idx, mut greater) = (low, low + 1, high);
+
+		while idx < greater {
+			if is_less(&v[idx], &v[equal])? {
+				v.swap(equal, idx);
+				equal += 1;
+				idx += 1;
+			} else if is_less(&v[equal], &v[idx])? {
+				greater -= 1;
+				v.swap(idx, greater);
+			} else {
+				idx += 1;
+			}
+		}
+
+		if index < equal {
+			high = equal;
+		} else if index >= greater {
+			low = greater;
+		} else {
+			return Ok(());
+		}
+	}
+}
+
+/// The elements before, at, and after the index passed to
+/// [`try_select_nth_unstable`](TrySliceExt::try_select_nth_unstable).
+pub type SelectNth<'a, T> = (&'a mut [T], &'a mut T, &'a mut [T]);
+
+/// Fallible versions of [`slice`] methods that compare elements.
+pub trait TrySliceExt<T> {
+	/// The fallible equivalent of `==` on slices.
+	fn try_eq_slices<U>(&self, other: &[U]) -> Result<bool, T::Error>
+	where
+		T: TryPartialEq<U>;
+
+	/// The fallible equivalent of [`slice::contains`].
+	fn try_contains(&self, x: &T) -> Result<bool, T::Error>
+	where
+		T: TryPartialEq;
+
+	/// Returns the index of the first element equal to `x`, if there is one.
+	fn try_position(&self, x: &T) -> Result<Option<usize>, T::Error>
+	where
+		T: TryPartialEq;
+
+	/// The fallible equivalent of [`slice::starts_with`].
+	fn try_starts_with(&self, needle: &[T]) -> Result<bool, T::Error>
+	where
+		T: TryPartialEq;
+
+	/// The fallible equivalent of [`slice::ends_with`].
+	fn try_ends_with(&self, needle: &[T]) -> Result<bool, T::Error>
+	where
+		T: TryPartialEq;
+
+	/// Returns an iterator over the subslices separated by elements equal to `separator`.
+	///
+	/// This is like [`slice::split`], except it splits on an element rather than a predicate. If
+	/// a comparison fails, the error is yielded and the iterator ends.
+	fn try_split<'a>(&'a self, separator: &'a T) -> TrySplit<'a, T>
+	where
+		T: TryPartialEq;
+
+	/// The fallible equivalent of [`slice::sort`].
+	fn try_sort(&mut self) -> Result<(), T::OrdError>
+	where
+		T: TryOrd;
+
+	/// The fallible equivalent of [`slice::sort_by`].
+	fn try_sort_by<E, F>(&mut self, compare: F) -> Result<(), E>
+	where
+		F: FnMut(&T, &T) -> Result<Ordering, E>;
+
+	/// The fallible equivalent of [`slice::sort_by_key`].
+	///
+	/// Comparison errors are converted into the key function's error type.
+	fn try_sort_by_key<K, E, F>(&mut self, f: F) -> Result<(), E>
+	where
+		F: FnMut(&T) -> Result<K, E>,
+		K: TryOrd,
+		K::OrdError: Into<E>;
+
+	/// The fallible equivalent of [`slice::sort_unstable`].
+	fn try_sort_unstable(&mut self) -> Result<(), T::OrdError>
+	where
+		T: TryOrd;
+
+	/// The fallible equivalent of [`slice::sort_unstable_by`].
+	fn try_sort_unstable_by<E, F>(&mut self, compare: F) -> Result<(), E>
+	where
+		F: FnMut(&T, &T) -> Result<Ordering, E>;
+
+	/// The fallible equivalent of [`slice::binary_search`].
+	fn try_binary_search(&self, x: &T) -> Result<Result<usize, usize>, T::OrdError>
+	where
+		T: TryOrd;
+
+	/// The fallible equivalent of [`slice::binary_search_by`].
+	fn try_binary_search_by<E, F>(&self, f: F) -> Result<Result<usize, usize>, E>
+	where
+		F: FnMut(&T) -> Result<Ordering, E>;
+
+	/// The fallible equivalent of [`slice::binary_search_by_key`].
+	///
+	/// Comparison errors are converted into the key function's error type.
+	fn try_binary_search_by_key<K, E, F>(&self, key: &K, f: F) -> Result<Result<usize, usize>, E>
+	where
+		F: FnMut(&T) -> Result<K, E>,
+		K: TryOrd,
+		K::OrdError: Into<E>;
+
+	/// The fallible equivalent of [`slice::partition_point`].
+	fn try_partition_point<E, F>(&self, pred: F) -> Result<usize, E>
+	where
+		F: FnMut(&T) -> Result<bool, E>;
+
+	/// The fallible equivalent of [`slice::select_nth_unstable`].
+	///
+	/// # Panics
+	///
+	/// Panics if `index >= len()`.
+	fn try_select_nth_unstable(&mut self, index: usize)
+		-> Result<SelectNth<'_, T>, T::OrdError>
+	where
+		T: TryOrd;
+
+	/// The fallible equivalent of [`slice::is_sorted`].
+	///
+	/// Like `is_sorted`, this returns `false` if any two neighbouring elements are incomparable.
+	fn try_is_sorted(&self) -> Result<bool, T::OrdError>
+	where
+		T: TryPartialOrd;
+}
+
+impl<T> TrySliceExt<T> for [T] {
+	fn try_eq_slices<U>(&self, other: &[U]) -> Result<bool, T::Error>
+	where
+		T: TryPartialEq<U>
+	{
+		if self.len() != other.len() {
+			return Ok(false);
+		}
+
+		for (lhs, rhs) in self.iter().zip(other) {
+			if !lhs.try_eq(rhs)? {
+				return Ok(false);
+			}
+		}
+
+		Ok(true)
+	}
+
+	#[inline]
+	fn try_contains(&self, x: &T) -> Result<bool, T::Error>
+	where
+		T: TryPartialEq
+	{
+		Ok(self.try_position(x)?.is_some())
+	}
+
+	fn try_position(&self, x: &T) -> Result<Option<usize>, T::Error>
+	where
+		T: TryPartialEq
+	{
+		for (idx, element) in self.iter().enumerate() {
+			if element.try_eq(x)? {
+				return Ok(Some(idx));
+			}
+		}
+
+		Ok(None)
+	}
+
+	fn try_starts_with(&self, needle: &[T]) -> Result<bool, T::Error>
+	where
+		T: TryPartialEq
+	{
+		match self.get(..needle.len()) {
+			Some(start) => start.try_eq_slices(needle),
+			None => Ok(false)
+		}
+	}
+
+	fn try_ends_with(&self, needle: &[T]) -> Result<bool, T::Error>
+	where
+		T: TryPartialEq
+	{
+		match self.len().checked_sub(needle.len()) {
+			Some(start) => self[start..].try_eq_slices(needle),
+			None => Ok(false)
+		}
+	}
+
+	#[inline]
+	fn try_split<'a>(&'a self, separator: &'a T) -> TrySplit<'a, T>
+	where
+		T: TryPartialEq
+	{
+		TrySplit { rest: Some(self), separator }
+	}
+
+	#[inline]
+	fn try_sort(&mut self) -> Result<(), T::OrdError>
+	where
+		T: TryOrd
+	{
+		merge_sort(self, &mut T::try_lt)
+	}
+
+	fn try_sort_by<E, F>(&mut self, mut compare: F) -> Result<(), E>
+	where
+		F: FnMut(&T, &T) -> Result<Ordering, E>
+	{
+		merge_sort(self, &mut |lhs, rhs| Ok(compare(lhs, rhs)? == Ordering::Less))
+	}
+
+	fn try_sort_by_key<K, E, F>(&mut self, mut f: F) -> Result<(), E>
+	where
+		F: FnMut(&T) -> Result<K, E>,
+		K: TryOrd,
+		K::OrdError: Into<E>
+	{
+		merge_sort(self, &mut |lhs, rhs| f(lhs)?.try_lt(&f(rhs)?).map_err(Into::into))
+	}
+
+	#[inline]
+	fn try_sort_unstable(&mut self) -> Result<(), T::OrdError>
+	where
+		T: TryOrd
+	{
+		heapsort(self, &mut T::try_lt)
+	}
+
+	fn try_sort_unstable_by<E, F>(&mut self, mut compare: F) -> Result<(), E>
+	where
+		F: FnMut(&T, &T) -> Result<Ordering, E>
+	{
+		heapsort(self, &mut |lhs, rhs| Ok(compare(lhs, rhs)? == Ordering::Less))
+	}
+
+	#[inline]
+	fn try_binary_search(&self, x: &T) -> Result<Result<usize, usize>, T::OrdError>
+	where
+		T: TryOrd
+	{
+		self.try_binary_search_by(|element| element.try_cmp(x))
+	}
+
+	fn try_binary_search_by<E, F>(&self, mut f: F) -> Result<Result<usize, usize>, E>
+	where
+		F: FnMut(&T) -> Result<Ordering, E>
+	{
+		let (mut low, mut high) = (0, self.len());
+
+		while low < high {
+			let mid = low + (high - low) / 2;
+
+			match f(&self[mid])? {
+				Ordering::Less => low = mid + 1,
+				Ordering::Greater => high = mid,
+				Ordering::Equal => return Ok(Ok(mid))
+			}
+		}
+
+		Ok(Err(low))
+	}
+
+	fn try_binary_search_by_key<K, E, F>(&self, key: &K, mut f: F) -> Result<Result<usize, usize>, E>
+	where
+		F: FnMut(&T) -> Result<K, E>,
+		K: TryOrd,
+		K::OrdError: Into<E>
+	{
+		self.try_binary_search_by(|element| f(element)?.try_cmp(key).map_err(Into::into))
+	}
+
+	#[inline]
+	fn try_partition_point<E, F>(&self, pred: F) -> Result<usize, E>
+	where
+		F: FnMut(&T) -> Result<bool, E>
+	{
+		partition_point(self, pred)
+	}
+
+	fn try_select_nth_unstable(&mut self, index: usize)
+		-> Result<SelectNth<'_, T>, T::OrdError>
+	where
+		T: TryOrd
+	{
+		let len = self.len();
+		assert!(index < len, "partition index {} is out of bounds for length {}", index, len);
+
+		select_nth(self, index, &mut T::try_lt)?;
+
+		let (left, rest) = self.split_at_mut(index);
+		let (nth, right) = rest.split_first_mut().expect("index is in bounds");
+		Ok((left, nth, right))
+	}
+
+	fn try_is_sorted(&self) -> Result<bool, T::OrdError>
+	where
+		T: TryPartialOrd
+	{
+		for pair in self.windows(2) {
+			match pair[0].try_partial_cmp(&pair[1])? {
+				Some(Ordering::Less) | Some(Ordering::Equal) => {},
+				Some(Ordering::Greater) | None => return Ok(false)
+			}
+		}
+
+		Ok(true)
+	}
+}
+
+/// The iterator returned by [`TrySliceExt::try_split`].
+#[derive(Debug, Clone)]
+pub struct TrySplit<'a, T> {
+	rest: Option<&'a [T]>,
+	separator: &'a T
+}
+
+impl<'a, T: TryPartialEq> Iterator for TrySplit<'a, T> {
+	type Item = Result<&'a [T], T::Error>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let rest = self.rest.take()?;
+
+		match rest.try_position(self.separator) {
+			Ok(Some(idx)) => {
+				self.rest = Some(&rest[idx + 1..]);
+				Some(Ok(&rest[..idx]))
+			},
+			Ok(None) => Some(Ok(rest)),
+			Err(err) => Some(Err(err))
+		}
+	}
+}
+
+impl<T: TryPartialEq> FusedIterator for TrySplit<'_, T> {}
+
+/// Fallible versions of [`Vec`] methods that compare elements.
+///
+/// This is only available with the `alloc` feature.
+#[cfg(feature = "alloc")]
+pub trait TryVecExt<T> {
+	/// The fallible equivalent of [`Vec::dedup`].
+	///
+	/// If a comparison fails, the duplicates found so far are removed, and the rest of the vector
+	/// is left as-is.
+	fn try_dedup(&mut self) -> Result<(), T::Error>
+	where
+		T: TryPartialEq;
+
+	/// The fallible equivalent of [`Vec::dedup_by`].
+	///
+	/// If `same_bucket` fails, the duplicates found so far are removed, and the rest of the vector
+	/// is left as-is.
+	fn try_dedup_by<E, F>(&mut self, same_bucket: F) -> Result<(), E>
+	where
+		F: FnMut(&mut T, &mut T) -> Result<bool, E>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> TryVecExt<T> for Vec<T> {
+	#[inline]
+	fn try_dedup(&mut self) -> Result<(), T::Error>
+	where
+		T: TryPartialEq
+	{
+		self.try_dedup_by(|current, previous| current.try_eq(previous))
+	}
+
+	fn try_dedup_by<E, F>(&mut self, mut same_bucket: F) -> Result<(), E>
+	where
+		F: FnMut(&mut T, &mut T) -> Result<bool, E>
+	{
+		// `self[..write]` are the elements being kept, `self[write..read]` are duplicates, and
+		// `self[read..]` haven't been looked at yet.
+		let mut write = 1;
+
+		for read in 1..self.len() {
+			let (kept, rest) = self.split_at_mut(read);
+
+			match same_bucket(&mut rest[0], &mut kept[write - 1]) {
+				Ok(true) => {},
+				Ok(false) => {
+					self.swap(read, write);
+					write += 1;
+				},
+				Err(err) => {
+					self.drain(write..read);
+					return Err(err);
+				}
+			}
+		}
+
+		self.truncate(write);
+		Ok(())
+	}
+}
+
+/// Storage that can be viewed as a slice, such as slices, arrays, and `Vec`s (or references to
+/// them).
+///
+/// This is used by the indexing views in [`ops`](crate::ops), such as [`Checked`](
+/// crate::ops::Checked).
+pub trait SliceLike {
+	/// The type of the elements.
+	type Item;
+
+	/// Views `self` as a slice.
+	fn as_slice(&self) -> &[Self::Item];
+}
+
+/// A [`SliceLike`] that can also be viewed as a mutable slice.
+pub trait SliceLikeMut : SliceLike {
+	/// Views `self` as a mutable slice.
+	fn as_mut_slice(&mut self) -> &mut [Self::Item];
+}
+
+impl<T> SliceLike for [T] {
+	type Item = T;
+
+	#[inline]
+	fn as_slice(&self) -> &[T] {
+		self
+	}
+}
+
+impl<T> SliceLikeMut for [T] {
+	#[inline]
+	fn as_mut_slice(&mut self) -> &mut [T] {
+		self
+	}
+}
+
+impl<T, const N: usize> SliceLike for [T; N] {
+	type Item = T;
+
+	#[inline]
+	fn as_slice(&self) -> &[T] {
+		self
+	}
+}
+
+impl<T, const N: usize> SliceLikeMut for [T; N] {
+	#[inline]
+	fn as_mut_slice(&mut self) -> &mut [T] {
+		self
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> SliceLike for Vec<T> {
+	type Item = T;
+
+	#[inline]
+	fn as_slice(&self) -> &[T] {
+		self
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> SliceLikeMut for Vec<T> {
+	#[inline]
+	fn as_mut_slice(&mut self) -> &mut [T] {
+		self
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<S: ?Sized + SliceLike> SliceLike for alloc::boxed::Box<S> {
+	type Item = S::Item;
+
+	#[inline]
+	fn as_slice(&self) -> &[S::Item] {
+		(**self).as_slice()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<S: ?Sized + SliceLikeMut> SliceLikeMut for alloc::boxed::Box<S> {
+	#[inline]
+	fn as_mut_slice(&mut self) -> &mut [S::Item] {
+		(**self).as_mut_slice()
+	}
+}
+
+impl<S: ?Sized + SliceLike> SliceLike for &S {
+	type Item = S::Item;
+
+	#[inline]
+	fn as_slice(&self) -> &[S::Item] {
+		(**self).as_slice()
+	}
+}
+
+impl<S: ?Sized + SliceLike> SliceLike for &mut S {
+	type Item = S::Item;
+
+	#[inline]
+	fn as_slice(&self) -> &[S::Item] {
+		(**self).as_slice()
+	}
+}
+
+impl<S: ?Sized + SliceLikeMut> SliceLikeMut for &mut S {
+	#[inline]
+	fn as_mut_slice(&mut self) -> &mut [S::Item] {
+		(**self).as_mut_slice()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::vec::Vec;
+	use crate::Infallible;
+	use crate::testing::{fallible, retry, sorted_values, values, Budget, Fallible};
+	use super::TrySliceExt;
+	#[cfg(feature = "alloc")]
+	use {crate::testing::OutOfBudget, super::TryVecExt};
+
+	fn unsorted() -> Vec<u32> {
+		(0..40).map(|idx| idx * 17 % 23).collect()
+	}
+
+	#[test]
+	fn failed_sorts_leave_a_permutation() {
+		let budget = Budget::new(usize::MAX);
+		let mut expected = unsorted();
+		expected.sort_unstable();
+
+		let mut v = fallible(&unsorted(), &budget);
+		retry(&mut v[..], &budget, sorted_values, <[Fallible]>::try_sort);
+		assert_eq!(values(&v), expected);
+
+		let mut v = fallible(&unsorted(), &budget);
+		retry(&mut v[..], &budget, sorted_values, <[Fallible]>::try_sort_unstable);
+		assert_eq!(values(&v), expected);
+
+		let mut v = fallible(&unsorted(), &budget);
+		let nth = retry(&mut v[..], &budget, sorted_values, |v| {
+			v.try_select_nth_unstable(25).map(|(_, nth, _)| nth.value)
+		});
+		assert_eq!(nth, expected[25]);
+	}
+
+	#[test]
+	fn sort_is_stable() {
+		let mut v: Vec<_> = unsorted().into_iter()
+			.enumerate()
+			.map(|(idx, key)| (key % 5, idx))
+			.collect();
+		let mut expected = v.clone();
+		expected.sort_by_key(|&(key, _)| key);
+
+		v.try_sort_by_key(|&(key, _)| Ok::<_, Infallible>(key)).unwrap();
+		assert_eq!(v, expected);
+	}
+
+	#[test]
+	fn select_nth_is_linear_for_equal_elements() {
+		let len = 1000;
+		let budget = Budget::new(usize::MAX);
+		let mut v = fallible(&std::vec![7; len], &budget);
+
+		budget.set(3 * len);
+		assert!(v.try_select_nth_unstable(len / 2).is_ok());
+	}
+
+	#[test]
+	fn incomparable_elements_are_unsorted() {
+		assert_eq!([1.0, 2.0, 2.0].try_is_sorted(), Ok(true));
+		assert_eq!([1.0, f64::NAN, 2.0].try_is_sorted(), Ok(false));
+	}
+
+	#[test]
+	fn split() {
+		let split: Result<Vec<_>, _> = [1, 0, 2, 3, 0].try_split(&0).collect();
+		assert_eq!(split, Ok(std::vec![&[1][..], &[2, 3], &[]]));
+
+		let split: Result<Vec<_>, _> = [0, 0].try_split(&0).collect();
+		assert_eq!(split, Ok(std::vec![&[][..], &[], &[]]));
+
+		let split: Result<Vec<_>, _> = [0u32; 0].try_split(&0).collect();
+		assert_eq!(split, Ok(std::vec![&[][..]]));
+	}
+
+	#[cfg(feature = "alloc")]
+	#[test]
+	fn failed_dedup_removes_the_duplicates_found_so_far() {
+		let budget = Budget::new(usize::MAX);
+		let items = || fallible(&[1, 1, 2, 2, 2, 3, 1, 1], &budget);
+
+		let mut v = items();
+		v.try_dedup().unwrap();
+		assert_eq!(values(&v), [1, 2, 3, 1]);
+
+		// the fourth comparison (of the third `2`) fails, after the second `1` and `2` were found
+		// to be duplicates.
+		let mut v = items();
+		budget.set(3);
+		assert_eq!(v.try_dedup(), Err(OutOfBudget));
+		assert_eq!(values(&v), [1, 2, 2, 3, 1, 1]);
+
+		// wherever it fails, only duplicates are removed.
+		for calls in 0..7 {
+			let mut v = items();
+			budget.set(calls);
+			assert_eq!(v.try_dedup(), Err(OutOfBudget));
+
+			budget.set(usize::MAX);
+			v.try_dedup().unwrap();
+			assert_eq!(values(&v), [1, 2, 3, 1]);
+		}
+	}
+}
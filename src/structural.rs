@@ -0,0 +1,428 @@
+//! Forwarding of the try traits through composite types and pointers.
+//!
+//! See [`Lexicographic`](crate::cmp::Lexicographic) for the comparison traits on composite types.
+//!
+//! # Why wrappers are needed
+//! Every type that implements a std trait (such as `(u32, u32)` or `Box<u32>`) already implements
+//! the corresponding try trait via a blanket implementation, so composite types and pointers
+//! can't be given implementations that defer to their elements' try traits instead. The wrappers
+//! [`Structural`], [`ByDeref`] and [`Lexicographic`](crate::cmp::Lexicographic) provide those
+//! implementations.
+//!
+//! # Error types
+//! As the elements of a composite type can have different error types, `Structural` and
+//! `Lexicographic` convert them all into a single error type `E`. Elements which only implement
+//! the std traits have an error type of [`Infallible`](crate::Infallible), so if any are present,
+//! `E` must implement `From<Infallible>`, which the
+//! [`impl_from_infallible!`](crate::impl_from_infallible) macro can implement:
+//!
+//! ```
+//! # use try_traits::cmp::{Lexicographic, TryPartialEq};
+//! #[derive(Debug)]
+//! struct KeyError;
+//! try_traits::impl_from_infallible!(KeyError);
+//!
+//! struct Key(u32);
+//!
+//! impl TryPartialEq for Key {
+//!     type Error = KeyError;
+//!
+//!     fn try_eq(&self, other: &Self) -> Result<bool, KeyError> {
+//!         Ok(self.0 == other.0)
+//!     }
+//! }
+//!
+//! let lhs = Lexicographic::<_, KeyError>::new((Key(1), 2_u32));
+//! let rhs = Lexicographic::new((Key(1), 2_u32));
+//! assert!(lhs.try_eq(&rhs).unwrap());
+//! ```
+
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{
+	Deref, DerefMut,
+	Range, RangeFrom, RangeTo, RangeFull, RangeInclusive, RangeToInclusive
+};
+use crate::clone::TryClone;
+use crate::cmp::{TryPartialEq, TryEq, TryPartialOrd, TryOrd};
+use crate::default::TryDefault;
+use crate::hash::TryHash;
+use crate::ops::*;
+
+/// Implements [`TryClone`], [`TryHash`] and [`TryDefault`] for tuples, arrays, [`Option`]s and
+/// [`Result`]s using the try traits of their elements.
+///
+/// Elements are visited in order, and the first failure is returned; any elements that were
+/// already constructed are dropped. Their errors are converted into `E` (see the
+/// [module docs](self#error-types)).
+#[repr(transparent)]
+pub struct Structural<T, E>(pub T, PhantomData<fn() -> E>);
+
+impl<T, E> Structural<T, E> {
+	/// Wraps `value`.
+	#[inline]
+	pub const fn new(value: T) -> Self {
+		Self(value, PhantomData)
+	}
+
+	/// Unwraps the value.
+	#[inline]
+	pub fn into_inner(self) -> T {
+		self.0
+	}
+}
+
+impl<T: Debug, E> Debug for Structural<T, E> {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.debug_tuple("Structural").field(&self.0).finish()
+	}
+}
+
+/// Creates an array by calling `f` on each index, stopping at the first error.
+fn try_array_from_fn<T, E, F, const N: usize>(mut f: F) -> Result<[T; N], E>
+where
+	F: FnMut(usize) -> Result<T, E>
+{
+	let mut error = None;
+	let elements: [Option<T>; N] = core::array::from_fn(|idx| {
+		if error.is_some() {
+			return None;
+		}
+
+		f(idx).map_err(|err| error = Some(err)).ok()
+	});
+
+	// if there was an error, `elements` is dropped here, dropping all the constructed elements.
+	if let Some(err) = error {
+		return Err(err);
+	}
+
+	Ok(elements.map(|element| element.expect("all elements were constructed")))
+}
+
+impl<T: TryClone, E, const N: usize> TryClone for Structural<[T; N], E>
+where
+	T::Error: Into<E>
+{
+	type Error = E;
+
+	fn try_clone(&self) -> Result<Self, Self::Error> {
+		try_array_from_fn(|idx| self.0[idx].try_clone().map_err(Into::into)).map(Self::new)
+	}
+}
+
+impl<T: TryHash, E, const N: usize> TryHash for Structural<[T; N], E>
+where
+	T::Error: Into<E>
+{
+	type Error = E;
+
+	fn try_hash<H: Hasher>(&self, state: &mut H) -> Result<(), Self::Error> {
+		// the same as how slices (and thus arrays) are hashed.
+		state.write_usize(N);
+		T::try_hash_slice(&self.0, state).map_err(Into::into)
+	}
+}
+
+impl<T: TryDefault, E, const N: usize> TryDefault for Structural<[T; N], E>
+where
+	T::Error: Into<E>
+{
+	type Error = E;
+
+	fn try_default() -> Result<Self, Self::Error> {
+		try_array_from_fn(|_| T::try_default().map_err(Into::into)).map(Self::new)
+	}
+}
+
+impl<T: TryClone, E> TryClone for Structural<Option<T>, E>
+where
+	T::Error: Into<E>
+{
+	type Error = E;
+
+	fn try_clone(&self) -> Result<Self, Self::Error> {
+		match &self.0 {
+			Some(value) => Ok(Self::new(Some(value.try_clone().map_err(Into::into)?))),
+			None => Ok(Self::new(None))
+		}
+	}
+}
+
+impl<T: TryHash, E> TryHash for Structural<Option<T>, E>
+where
+	T::Error: Into<E>
+{
+	type Error = E;
+
+	fn try_hash<H: Hasher>(&self, state: &mut H) -> Result<(), Self::Error> {
+		core::mem::discriminant(&self.0).hash(state);
+
+		match &self.0 {
+			Some(value) => value.try_hash(state).map_err(Into::into),
+			None => Ok(())
+		}
+	}
+}
+
+impl<T, E> TryDefault for Structural<Option<T>, E> {
+	type Error = E;
+
+	#[inline]
+	fn try_default() -> Result<Self, Self::Error> {
+		Ok(Self::new(None))
+	}
+}
+
+impl<T: TryClone, U: TryClone, E> TryClone for Structural<Result<T, U>, E>
+where
+	T::Error: Into<E>,
+	U::Error: Into<E>
+{
+	type Error = E;
+
+	fn try_clone(&self) -> Result<Self, Self::Error> {
+		match &self.0 {
+			Ok(value) => Ok(Self::new(Ok(value.try_clone().map_err(Into::into)?))),
+			Err(err) => Ok(Self::new(Err(err.try_clone().map_err(Into::into)?)))
+		}
+	}
+}
+
+impl<T: TryHash, U: TryHash, E> TryHash for Structural<Result<T, U>, E>
+where
+	T::Error: Into<E>,
+	U::Error: Into<E>
+{
+	type Error = E;
+
+	fn try_hash<H: Hasher>(&self, state: &mut H) -> Result<(), Self::Error> {
+		core::mem::discriminant(&self.0).hash(state);
+
+		match &self.0 {
+			Ok(value) => value.try_hash(state).map_err(Into::into),
+			Err(err) => err.try_hash(state).map_err(Into::into)
+		}
+	}
+}
+
+macro_rules! impl_tuple {
+	($($name:ident $idx:tt)+) => {
+		impl<$($name: TryClone,)+ E> TryClone for Structural<($($name,)+), E>
+		where
+			$($name::Error: Into<E>),+
+		{
+			type Error = E;
+
+			fn try_clone(&self) -> Result<Self, Self::Error> {
+				Ok(Self::new(($((self.0).$idx.try_clone().map_err(Into::into)?,)+)))
+			}
+		}
+
+		impl<$($name: TryHash,)+ E> TryHash for Structural<($($name,)+), E>
+		where
+			$($name::Error: Into<E>),+
+		{
+			type Error = E;
+
+			fn try_hash<Hs: Hasher>(&self, state: &mut Hs) -> Result<(), Self::Error> {
+				$((self.0).$idx.try_hash(state).map_err(Into::into)?;)+
+
+				Ok(())
+			}
+		}
+
+		impl<$($name: TryDefault,)+ E> TryDefault for Structural<($($name,)+), E>
+		where
+			$($name::Error: Into<E>),+
+		{
+			type Error = E;
+
+			fn try_default() -> Result<Self, Self::Error> {
+				Ok(Self::new(($($name::try_default().map_err(Into::into)?,)+)))
+			}
+		}
+	};
+}
+
+impl_tuple!(A 0);
+impl_tuple!(A 0 B 1);
+impl_tuple!(A 0 B 1 C 2);
+impl_tuple!(A 0 B 1 C 2 D 3);
+impl_tuple!(A 0 B 1 C 2 D 3 F 4);
+impl_tuple!(A 0 B 1 C 2 D 3 F 4 G 5);
+impl_tuple!(A 0 B 1 C 2 D 3 F 4 G 5 H 6);
+impl_tuple!(A 0 B 1 C 2 D 3 F 4 G 5 H 6 I 7);
+impl_tuple!(A 0 B 1 C 2 D 3 F 4 G 5 H 6 I 7 J 8);
+impl_tuple!(A 0 B 1 C 2 D 3 F 4 G 5 H 6 I 7 J 8 K 9);
+impl_tuple!(A 0 B 1 C 2 D 3 F 4 G 5 H 6 I 7 J 8 K 9 L 10);
+impl_tuple!(A 0 B 1 C 2 D 3 F 4 G 5 H 6 I 7 J 8 K 9 L 10 M 11);
+
+/// Forwards the try traits through a pointer (such as `Box`, `Rc`, `Arc` or a reference) to the
+/// value it points to.
+///
+/// For example, `ByDeref<Box<T>>` implements [`TryPartialEq`] if `T` does. (See the
+/// [module docs](self#why-wrappers-are-needed) for why this is a wrapper.)
+///
+/// Like std's forwarding for references, the binary operator traits are implemented between two
+/// `&ByDeref<P>`s if they're implemented between two `&P::Target`s, and `ByDeref<P>` implements
+/// the assignment traits with a `&ByDeref<P>` if `P::Target` does with a `&P::Target`. (Other
+/// right-hand sides aren't possible, as they'd conflict with the automatic implementations.) For
+/// the same reason, [`TryIndex`] and [`TryIndexMut`] are only forwarded for `usize` and ranges of
+/// `usize`.
+#[derive(Debug, Clone, Copy, Default)]
+#[repr(transparent)]
+pub struct ByDeref<P>(pub P);
+
+impl<P: Deref> TryPartialEq for ByDeref<P>
+where
+	P::Target: TryPartialEq
+{
+	type Error = <P::Target as TryPartialEq>::Error;
+
+	#[inline]
+	fn try_eq(&self, other: &Self) -> Result<bool, Self::Error> {
+		(*self.0).try_eq(&*other.0)
+	}
+
+	#[inline]
+	fn try_ne(&self, other: &Self) -> Result<bool, Self::Error> {
+		(*self.0).try_ne(&*other.0)
+	}
+}
+
+impl<P: Deref> TryEq for ByDeref<P> where P::Target: TryEq {}
+
+impl<P: Deref> TryPartialOrd for ByDeref<P>
+where
+	P::Target: TryPartialOrd
+{
+	type OrdError = <P::Target as TryPartialOrd>::OrdError;
+
+	#[inline]
+	fn try_partial_cmp(&self, other: &Self) -> Result<Option<core::cmp::Ordering>, Self::OrdError> {
+		(*self.0).try_partial_cmp(&*other.0)
+	}
+
+	#[inline]
+	fn try_lt(&self, other: &Self) -> Result<bool, Self::OrdError> {
+		(*self.0).try_lt(&*other.0)
+	}
+
+	#[inline]
+	fn try_le(&self, other: &Self) -> Result<bool, Self::OrdError> {
+		(*self.0).try_le(&*other.0)
+	}
+
+	#[inline]
+	fn try_gt(&self, other: &Self) -> Result<bool, Self::OrdError> {
+		(*self.0).try_gt(&*other.0)
+	}
+
+	#[inline]
+	fn try_ge(&self, other: &Self) -> Result<bool, Self::OrdError> {
+		(*self.0).try_ge(&*other.0)
+	}
+}
+
+impl<P: Deref> TryOrd for ByDeref<P>
+where
+	P::Target: TryOrd
+{
+	#[inline]
+	fn try_cmp(&self, other: &Self) -> Result<core::cmp::Ordering, Self::OrdError> {
+		(*self.0).try_cmp(&*other.0)
+	}
+}
+
+impl<P: Deref> TryHash for ByDeref<P>
+where
+	P::Target: TryHash
+{
+	type Error = <P::Target as TryHash>::Error;
+
+	#[inline]
+	fn try_hash<H: Hasher>(&self, state: &mut H) -> Result<(), Self::Error> {
+		(*self.0).try_hash(state)
+	}
+}
+
+macro_rules! forward_index {
+	($($idx:ty),* $(,)?) => {$(
+		impl<P: Deref> TryIndex<$idx> for ByDeref<P>
+		where
+			P::Target: TryIndex<$idx>
+		{
+			type Error = <P::Target as TryIndex<$idx>>::Error;
+			type Output = <P::Target as TryIndex<$idx>>::Output;
+
+			#[inline]
+			fn try_index(&self, index: $idx) -> Result<&Self::Output, Self::Error> {
+				(*self.0).try_index(index)
+			}
+		}
+
+		impl<P: DerefMut> TryIndexMut<$idx> for ByDeref<P>
+		where
+			P::Target: TryIndexMut<$idx>
+		{
+			type MutError = <P::Target as TryIndexMut<$idx>>::MutError;
+
+			#[inline]
+			fn try_index_mut(&mut self, index: $idx) -> Result<&mut Self::Output, Self::MutError> {
+				(*self.0).try_index_mut(index)
+			}
+		}
+	)*};
+}
+
+forward_index! {
+	usize, Range<usize>, RangeFrom<usize>, RangeTo<usize>, RangeFull,
+	RangeInclusive<usize>, RangeToInclusive<usize>,
+}
+
+macro_rules! forward_binary_ops {
+	($($trait:ident $method:ident),* $(,)?) => {$(
+		impl<'a, P: Deref> $trait for &'a ByDeref<P>
+		where
+			&'a P::Target: $trait<&'a P::Target>
+		{
+			type Error = <&'a P::Target as $trait<&'a P::Target>>::Error;
+			type Output = <&'a P::Target as $trait<&'a P::Target>>::Output;
+
+			#[inline]
+			fn $method(self, other: Self) -> Result<Self::Output, Self::Error> {
+				(&*self.0).$method(&*other.0)
+			}
+		}
+	)*};
+}
+
+macro_rules! forward_assign_ops {
+	($($trait:ident $method:ident),* $(,)?) => {$(
+		impl<'a, P: DerefMut> $trait<&'a ByDeref<P>> for ByDeref<P>
+		where
+			P::Target: $trait<&'a P::Target>
+		{
+			type Error = <P::Target as $trait<&'a P::Target>>::Error;
+
+			#[inline]
+			fn $method(&mut self, other: &'a Self) -> Result<(), Self::Error> {
+				(*self.0).$method(&*other.0)
+			}
+		}
+	)*};
+}
+
+forward_binary_ops! {
+	TryAdd try_add, TrySub try_sub, TryMul try_mul, TryDiv try_div, TryRem try_rem,
+	TryBitAnd try_bitand, TryBitOr try_bitor, TryBitXor try_bitxor, TryShl try_shl, TryShr try_shr,
+}
+
+forward_assign_ops! {
+	TryAddAssign try_add_assign, TrySubAssign try_sub_assign, TryMulAssign try_mul_assign,
+	TryDivAssign try_div_assign, TryRemAssign try_rem_assign,
+	TryBitAndAssign try_bitand_assign, TryBitOrAssign try_bitor_assign,
+	TryBitXorAssign try_bitxor_assign, TryShlAssign try_shl_assign, TryShrAssign try_shr_assign,
+}
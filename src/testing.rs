@@ -0,0 +1,119 @@
+//! Helpers shared by the unit tests.
+
+// which of these are used depends on the enabled features.
+#![allow(dead_code)]
+
+use core::cell::Cell;
+use core::cmp::Ordering;
+use core::fmt::Debug;
+use core::hash::Hasher;
+use std::vec::Vec;
+use crate::cmp::{TryEq, TryOrd, TryPartialEq, TryPartialOrd};
+use crate::hash::TryHash;
+
+/// The error returned once a [`Budget`] has run out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfBudget;
+
+/// How many more comparisons (and hashes) [`Fallible`] values can do before they start failing.
+pub type Budget = Cell<usize>;
+
+/// A number whose comparisons and hashing fail once its budget has run out.
+#[derive(Debug, Clone, Copy)]
+pub struct Fallible<'b> {
+	pub value: u32,
+	budget: &'b Budget
+}
+
+impl<'b> Fallible<'b> {
+	pub fn new(value: u32, budget: &'b Budget) -> Self {
+		Self { value, budget }
+	}
+
+	fn spend(&self) -> Result<(), OutOfBudget> {
+		let left = self.budget.get().checked_sub(1).ok_or(OutOfBudget)?;
+		self.budget.set(left);
+		Ok(())
+	}
+}
+
+/// Wraps each of `values` in a [`Fallible`] sharing `budget`.
+pub fn fallible<'b>(values: &[u32], budget: &'b Budget) -> Vec<Fallible<'b>> {
+	values.iter().map(|&value| Fallible::new(value, budget)).collect()
+}
+
+/// Returns the values of `items`, in order.
+pub fn values<'a, 'b: 'a>(items: impl IntoIterator<Item = &'a Fallible<'b>>) -> Vec<u32> {
+	items.into_iter().map(|item| item.value).collect()
+}
+
+/// Returns the values of `items`, sorted. If this doesn't change, `items` has only been permuted.
+pub fn sorted_values(items: &[Fallible]) -> Vec<u32> {
+	let mut values = values(items);
+	values.sort_unstable();
+	values
+}
+
+/// Runs `op` on `target` with a budget of 0, 1, 2, ... calls until it succeeds, and returns its
+/// result. Every time it fails, `snapshot(target)` must still be what it was beforehand.
+pub fn retry<C: ?Sized, S, R, E>(
+	target: &mut C,
+	budget: &Budget,
+	snapshot: impl Fn(&C) -> S,
+	mut op: impl FnMut(&mut C) -> Result<R, E>
+) -> R
+where
+	S: PartialEq + Debug
+{
+	let before = snapshot(target);
+	let mut calls = 0;
+
+	loop {
+		budget.set(calls);
+
+		match op(target) {
+			Ok(result) => {
+				budget.set(usize::MAX);
+				return result;
+			},
+			Err(_) => assert_eq!(snapshot(target), before, "failed after {} calls", calls)
+		}
+
+		calls += 1;
+	}
+}
+
+impl TryPartialEq for Fallible<'_> {
+	type Error = OutOfBudget;
+
+	fn try_eq(&self, other: &Self) -> Result<bool, Self::Error> {
+		self.spend()?;
+		Ok(self.value == other.value)
+	}
+}
+
+impl TryEq for Fallible<'_> {}
+
+impl TryPartialOrd for Fallible<'_> {
+	type OrdError = OutOfBudget;
+
+	fn try_partial_cmp(&self, other: &Self) -> Result<Option<Ordering>, Self::OrdError> {
+		self.try_cmp(other).map(Some)
+	}
+}
+
+impl TryOrd for Fallible<'_> {
+	fn try_cmp(&self, other: &Self) -> Result<Ordering, Self::OrdError> {
+		self.spend()?;
+		Ok(self.value.cmp(&other.value))
+	}
+}
+
+impl TryHash for Fallible<'_> {
+	type Error = OutOfBudget;
+
+	fn try_hash<H: Hasher>(&self, state: &mut H) -> Result<(), Self::Error> {
+		self.spend()?;
+		Ok(state.write_u32(self.value))
+	}
+}